@@ -31,4 +31,142 @@ pub enum ErrorCode {
 
     #[msg("Insufficient vault balance")]
     InsufficientVaultBalance,
+
+    #[msg("Token account does not hold the governance mint")]
+    InvalidGovernanceMint,
+
+    #[msg("Token account is not owned by the voter")]
+    InvalidTokenOwner,
+
+    #[msg("Voter has no voting power")]
+    NoVotingPower,
+
+    #[msg("Lock amount must be greater than zero")]
+    InvalidLockAmount,
+
+    #[msg("Lock duration is outside the allowed range")]
+    InvalidLockDuration,
+
+    #[msg("Tokens are still locked")]
+    LockNotExpired,
+
+    #[msg("Lock expires before the voting period ends")]
+    LockExpiresBeforeVotingEnds,
+
+    #[msg("Invalid proposal category")]
+    InvalidCategory,
+
+    #[msg("Invalid delegate")]
+    InvalidDelegate,
+
+    #[msg("Delegation does not cover this proposal's category")]
+    DelegationCategoryMismatch,
+
+    #[msg("Delegated accounts are missing or do not match")]
+    InvalidDelegationAccounts,
+
+    #[msg("Delegator has already voted on this proposal")]
+    DelegatorAlreadyVoted,
+
+    #[msg("Delegated weight has already been cast on this proposal")]
+    DelegationAlreadyUsed,
+
+    #[msg("Not allowed in this proposal's voting mode")]
+    InvalidVotingMode,
+
+    #[msg("Not enough voice credits")]
+    InsufficientCredits,
+
+    #[msg("Invalid governance parameters")]
+    InvalidGovernanceParams,
+
+    #[msg("Proposal status does not allow this transition")]
+    InvalidStatusTransition,
+
+    #[msg("Proposal is not open for voting")]
+    ProposalNotActive,
+
+    #[msg("Milestones must be non-zero and sum to the requested amount")]
+    InvalidMilestones,
+
+    #[msg("Milestone index out of range")]
+    InvalidMilestoneIndex,
+
+    #[msg("Milestone status does not allow this action")]
+    InvalidMilestoneStatus,
+
+    #[msg("No approved funds left to claim")]
+    NothingToClaim,
+
+    #[msg("Vesting schedule must satisfy start <= cliff <= end with start < end")]
+    InvalidVestingSchedule,
+
+    #[msg("Proposal uses a different payout mode")]
+    InvalidPayoutMode,
+
+    #[msg("Vesting already cancelled")]
+    VestingAlreadyCancelled,
+
+    #[msg("Proposal requests a different asset")]
+    InvalidRequestedMint,
+
+    #[msg("Round windows must be ordered and the budget non-zero")]
+    InvalidRoundSchedule,
+
+    #[msg("Round is not accepting applications")]
+    RoundNotAccepting,
+
+    #[msg("Round payout deadline has passed")]
+    PayoutDeadlinePassed,
+
+    #[msg("Deposit memo is too long")]
+    MemoTooLong,
+
+    #[msg("Depositor has no contribution left in this vault")]
+    NoContribution,
+
+    #[msg("Vault's free balance cannot cover this grant")]
+    InsufficientFreeBalance,
+
+    #[msg("Round payout deadline has not passed yet")]
+    PayoutDeadlineNotPassed,
+
+    #[msg("Emergency threshold must be at least 2 treasurers and timelock within bounds")]
+    InvalidEmergencyPolicy,
+
+    #[msg("Program is paused")]
+    ProgramPaused,
+
+    #[msg("Only allowed while the program is paused")]
+    ProgramNotPaused,
+
+    #[msg("Not enough admin approvals")]
+    NotEnoughApprovals,
+
+    #[msg("Timelock has not elapsed")]
+    TimelockNotElapsed,
+
+    #[msg("Amount must be greater than zero")]
+    InvalidAmount,
+
+    #[msg("Admin council must have 2-10 unique members and a reachable threshold")]
+    InvalidAdminSet,
+
+    #[msg("Admin change proposal has expired")]
+    AdminChangeExpired,
+
+    #[msg("Admin does not hold the role this action requires")]
+    MissingRole,
+
+    #[msg("Every role needs a holder and enough treasurers to reach the approval thresholds")]
+    InvalidRoles,
+
+    #[msg("Token grants need the vault's token ledger for the requested mint")]
+    TokenLedgerRequired,
+
+    #[msg("Token withdrawals need the mint, token program and both token accounts")]
+    TokenAccountsRequired,
+
+    #[msg("Grant would take the voter past the voice credit cap")]
+    VoiceCreditCapExceeded,
 }
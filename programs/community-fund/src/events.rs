@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+
+/// Result of comparing a vault's recorded totals with its real balance
+#[event]
+pub struct VaultReconciled {
+    pub vault: Pubkey,
+    pub lamports: u64,
+    /// Balance the recorded totals accounted for before this reconciliation
+    pub expected: u64,
+    /// Untracked inflow found now and added to `external_donations`
+    pub new_donations: u64,
+    /// Lamports missing from the vault - should always be zero
+    pub shortfall: u64,
+    pub external_donations: u64,
+    pub timestamp: i64,
+}
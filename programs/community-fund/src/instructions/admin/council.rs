@@ -0,0 +1,209 @@
+use anchor_lang::prelude::*;
+use crate::state::{AdminChange, AdminChangeProposal, Config, Fund};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct ProposeAdminChange<'info> {
+    #[account(seeds = [b"fund", fund.id.to_be_bytes().as_ref()], bump = fund.bump)]
+    pub fund: Account<'info, Fund>,
+
+    #[account(
+        mut,
+        seeds = [b"config", fund.key().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        seeds = [b"admin_change", fund.key().as_ref(), config.admin_change_count.to_be_bytes().as_ref()],
+        bump,
+        payer = admin,
+        space = 8 + AdminChangeProposal::INIT_SPACE
+    )]
+    pub admin_change: Account<'info, AdminChangeProposal>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Opens a council change; the proposer's approval counts towards the threshold
+pub fn propose_admin_change(ctx: Context<ProposeAdminChange>, change: AdminChange) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let admin = ctx.accounts.admin.key();
+    require!(config.admins.contains(&admin), ErrorCode::Unauthorized);
+
+    // Reject changes that could never be applied to the current council
+    let mut preview = (**config).clone();
+    preview.apply_admin_change(&change)?;
+
+    let current_time = Clock::get()?.unix_timestamp;
+    let admin_change = &mut ctx.accounts.admin_change;
+    admin_change.fund = ctx.accounts.fund.key();
+    admin_change.id = config.admin_change_count;
+    admin_change.proposer = admin;
+    admin_change.change = change;
+    admin_change.approvals = vec![admin];
+    admin_change.expires_at = current_time + AdminChangeProposal::APPROVAL_WINDOW;
+    admin_change.bump = ctx.bumps.admin_change;
+
+    config.admin_change_count += 1;
+
+    msg!(
+        "Admin change {} proposed, expires at {}",
+        admin_change.id,
+        admin_change.expires_at
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(change_id: u64)]
+pub struct ApproveAdminChange<'info> {
+    #[account(seeds = [b"fund", fund.id.to_be_bytes().as_ref()], bump = fund.bump)]
+    pub fund: Account<'info, Fund>,
+
+    #[account(seeds = [b"config", fund.key().as_ref()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"admin_change", fund.key().as_ref(), change_id.to_be_bytes().as_ref()],
+        bump = admin_change.bump
+    )]
+    pub admin_change: Account<'info, AdminChangeProposal>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn approve_admin_change(ctx: Context<ApproveAdminChange>, _change_id: u64) -> Result<()> {
+    let admin = ctx.accounts.admin.key();
+    let config = &ctx.accounts.config;
+    require!(config.admins.contains(&admin), ErrorCode::Unauthorized);
+
+    let admin_change = &mut ctx.accounts.admin_change;
+    let current_time = Clock::get()?.unix_timestamp;
+    require!(
+        !admin_change.is_expired(current_time),
+        ErrorCode::AdminChangeExpired
+    );
+    require!(
+        !admin_change.approvals.contains(&admin),
+        ErrorCode::AlreadyApproved
+    );
+    // Drop approvals from admins who have left so the list stays within MAX_ADMINS
+    admin_change.approvals.retain(|a| config.admins.contains(a));
+    admin_change.approvals.push(admin);
+
+    msg!(
+        "Admin change {} approved ({} approvals)",
+        admin_change.id,
+        admin_change.approvals.len()
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(change_id: u64)]
+pub struct ExecuteAdminChange<'info> {
+    #[account(seeds = [b"fund", fund.id.to_be_bytes().as_ref()], bump = fund.bump)]
+    pub fund: Account<'info, Fund>,
+
+    /// Resized to the council's new member count; the caller covers any extra rent
+    #[account(
+        mut,
+        seeds = [b"config", fund.key().as_ref()],
+        bump = config.bump,
+        realloc = Config::space(admin_change.change.admin_count_after(config.admins.len())),
+        realloc::payer = payer,
+        realloc::zero = false
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"admin_change", fund.key().as_ref(), change_id.to_be_bytes().as_ref()],
+        bump = admin_change.bump,
+        has_one = proposer,
+        close = proposer
+    )]
+    pub admin_change: Account<'info, AdminChangeProposal>,
+
+    /// CHECK: Receives the proposal's rent, matched by has_one
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+
+    /// Anyone can apply a change once the council has approved it
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn execute_admin_change(ctx: Context<ExecuteAdminChange>, _change_id: u64) -> Result<()> {
+    let admin_change = &ctx.accounts.admin_change;
+    let config = &mut ctx.accounts.config;
+
+    let current_time = Clock::get()?.unix_timestamp;
+    require!(
+        !admin_change.is_expired(current_time),
+        ErrorCode::AdminChangeExpired
+    );
+
+    // Approvals from admins who have since left the council don't count
+    require!(
+        config.count_approvals(&admin_change.approvals) >= config.admin_threshold as usize,
+        ErrorCode::NotEnoughApprovals
+    );
+
+    config.apply_admin_change(&admin_change.change)?;
+
+    msg!(
+        "Admin change {} applied, council is now {}-of-{}",
+        admin_change.id,
+        config.admin_threshold,
+        config.admins.len()
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(change_id: u64)]
+pub struct CancelAdminChange<'info> {
+    #[account(seeds = [b"fund", fund.id.to_be_bytes().as_ref()], bump = fund.bump)]
+    pub fund: Account<'info, Fund>,
+
+    #[account(seeds = [b"config", fund.key().as_ref()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"admin_change", fund.key().as_ref(), change_id.to_be_bytes().as_ref()],
+        bump = admin_change.bump,
+        has_one = proposer,
+        close = proposer
+    )]
+    pub admin_change: Account<'info, AdminChangeProposal>,
+
+    /// CHECK: Receives the proposal's rent, matched by has_one
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+
+    pub caller: Signer<'info>,
+}
+
+/// Any admin can drop a pending change; once expired anyone can clean it up
+pub fn cancel_admin_change(ctx: Context<CancelAdminChange>, _change_id: u64) -> Result<()> {
+    let admin_change = &ctx.accounts.admin_change;
+    let current_time = Clock::get()?.unix_timestamp;
+    require!(
+        admin_change.is_expired(current_time)
+            || ctx.accounts.config.admins.contains(&ctx.accounts.caller.key()),
+        ErrorCode::Unauthorized
+    );
+
+    msg!("Admin change {} cancelled", admin_change.id);
+    Ok(())
+}
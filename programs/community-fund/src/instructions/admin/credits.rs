@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use crate::state::{Config, Fund, VoiceCredits};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+#[instruction(voter: Pubkey)]
+pub struct GrantVoiceCredits<'info> {
+    #[account(seeds = [b"fund", fund.id.to_be_bytes().as_ref()], bump = fund.bump)]
+    pub fund: Account<'info, Fund>,
+
+    #[account(
+        init_if_needed,
+        seeds = [b"voice_credits", fund.key().as_ref(), voter.as_ref()],
+        bump,
+        payer = admin,
+        space = 8 + VoiceCredits::INIT_SPACE
+    )]
+    pub voice_credits: Account<'info, VoiceCredits>,
+
+    #[account(seeds = [b"config", fund.key().as_ref()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn grant_voice_credits(
+    ctx: Context<GrantVoiceCredits>,
+    voter: Pubkey,
+    amount: u64,
+) -> Result<()> {
+    let admin = ctx.accounts.admin.key();
+    ctx.accounts.config.require_role(&admin, Config::ROLE_PARAMS)?;
+    // Admins can't hand themselves quadratic voting power
+    require_keys_neq!(voter, admin, ErrorCode::Unauthorized);
+
+    let voice_credits = &mut ctx.accounts.voice_credits;
+    let balance = voice_credits
+        .balance
+        .checked_add(amount)
+        .filter(|balance| *balance <= VoiceCredits::MAX_BALANCE)
+        .ok_or(ErrorCode::VoiceCreditCapExceeded)?;
+    voice_credits.owner = voter;
+    voice_credits.balance = balance;
+    voice_credits.bump = ctx.bumps.voice_credits;
+
+    msg!("Granted {} voice credits to {}", amount, voter);
+    Ok(())
+}
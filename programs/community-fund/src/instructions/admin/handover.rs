@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+use crate::state::{AdminNomination, Config, Fund};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct NominateAdmin<'info> {
+    #[account(seeds = [b"fund", fund.id.to_be_bytes().as_ref()], bump = fund.bump)]
+    pub fund: Account<'info, Fund>,
+
+    #[account(seeds = [b"config", fund.key().as_ref()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// One pending nomination per seat
+    #[account(
+        init,
+        seeds = [b"admin_nomination", fund.key().as_ref(), admin.key().as_ref()],
+        bump,
+        payer = admin,
+        space = 8 + AdminNomination::INIT_SPACE
+    )]
+    pub nomination: Account<'info, AdminNomination>,
+
+    /// Current holder of the seat being handed over
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Offers the caller's own seat to `nominee`; nothing changes until they accept
+pub fn nominate_admin(ctx: Context<NominateAdmin>, nominee: Pubkey) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let admin = ctx.accounts.admin.key();
+    require!(config.admins.contains(&admin), ErrorCode::Unauthorized);
+    require!(!config.admins.contains(&nominee), ErrorCode::InvalidAdminSet);
+
+    let nomination = &mut ctx.accounts.nomination;
+    nomination.fund = ctx.accounts.fund.key();
+    nomination.admin = admin;
+    nomination.nominee = nominee;
+    nomination.nominated_at = Clock::get()?.unix_timestamp;
+    nomination.bump = ctx.bumps.nomination;
+
+    msg!("Admin {} nominated {} as successor", admin, nominee);
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(admin: Pubkey)]
+pub struct AcceptAdmin<'info> {
+    #[account(seeds = [b"fund", fund.id.to_be_bytes().as_ref()], bump = fund.bump)]
+    pub fund: Account<'info, Fund>,
+
+    #[account(
+        mut,
+        seeds = [b"config", fund.key().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"admin_nomination", fund.key().as_ref(), admin.as_ref()],
+        bump = nomination.bump,
+        has_one = nominee @ ErrorCode::Unauthorized,
+        close = outgoing_admin
+    )]
+    pub nomination: Account<'info, AdminNomination>,
+
+    /// CHECK: Receives the nomination's rent, must be the nominating admin
+    #[account(mut, address = admin @ ErrorCode::Unauthorized)]
+    pub outgoing_admin: UncheckedAccount<'info>,
+
+    /// Signing proves the nominee controls the key before it gets the seat
+    pub nominee: Signer<'info>,
+}
+
+pub fn accept_admin(ctx: Context<AcceptAdmin>, admin: Pubkey) -> Result<()> {
+    let nominee = ctx.accounts.nominee.key();
+
+    // Fails if the nominating admin left the council in the meantime
+    ctx.accounts.config.replace_admin(admin, nominee)?;
+
+    msg!("Admin seat handed over from {} to {}", admin, nominee);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CancelAdminNomination<'info> {
+    #[account(seeds = [b"fund", fund.id.to_be_bytes().as_ref()], bump = fund.bump)]
+    pub fund: Account<'info, Fund>,
+
+    #[account(
+        mut,
+        seeds = [b"admin_nomination", fund.key().as_ref(), admin.key().as_ref()],
+        bump = nomination.bump,
+        close = admin
+    )]
+    pub nomination: Account<'info, AdminNomination>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+/// Withdraws a nomination, e.g. one sent to a mistyped key
+pub fn cancel_admin_nomination(ctx: Context<CancelAdminNomination>) -> Result<()> {
+    msg!("Nomination of {} withdrawn", ctx.accounts.nomination.nominee);
+    Ok(())
+}
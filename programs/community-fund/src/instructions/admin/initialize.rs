@@ -1,21 +1,37 @@
 use anchor_lang::prelude::*;
-// use anchor_lang::solana_program::bpf_loader_upgradeable;  // Commented out for local testing
-use crate::state::Config;
-// use crate::errors::ErrorCode;  // Commented out for local testing
+#[cfg(not(feature = "local-testing"))]
+#[allow(deprecated)]
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+use anchor_spl::token_interface::Mint;
+use crate::state::{Config, EmergencyPolicy, Fund, GovernanceParams};
+#[cfg(not(feature = "local-testing"))]
+use crate::errors::ErrorCode;
 
 #[derive(Accounts)]
+#[instruction(fund_id: u64, admins: Vec<Pubkey>)]
 pub struct InitializeAdmin<'info> {
+    /// Fund realm - the config and every other account is derived under it
+    #[account(
+        init,
+        seeds = [b"fund", fund_id.to_be_bytes().as_ref()],
+        bump,
+        payer = user,
+        space = 8 + Fund::INIT_SPACE
+    )]
+    pub fund: Account<'info, Fund>,
+
     /// Config account - stores admin info
     #[account(
         init,
-        seeds = [b"config"],
+        seeds = [b"config", fund.key().as_ref()],
         bump,
         payer = user,
-        space = 8 + Config::INIT_SPACE
+        space = Config::space(admins.len())
     )]
     pub config: Account<'info, Config>,
 
-    /// User who is trying to initialize (must be upgrade authority)
+    /// Creates the fund and pays for it (must be upgrade authority).
+    /// Gets no council seat unless listed in `admins`.
     #[account(mut)]
     pub user: Signer<'info>,
 
@@ -23,60 +39,156 @@ pub struct InitializeAdmin<'info> {
     /// CHECK: We manually verify this is the correct program data account
     pub program_data: AccountInfo<'info>,
 
+    /// Governance token mint - voting weight is read from balances of this mint.
+    /// May belong to either the legacy Token program or Token-2022.
+    pub governance_mint: InterfaceAccount<'info, Mint>,
+
     pub system_program: Program<'info, System>,
 }
 
-pub fn initialize_admin(ctx: Context<InitializeAdmin>, admin2: Pubkey, admin3: Pubkey) -> Result<()> {
-    // COMMENTED OUT FOR LOCAL TESTING
-    // // Step 1: Derive expected program_data address from this executing program
-    // let program_id = ctx.program_id;
-    // let (expected_program_data, _bump) = Pubkey::find_program_address(
-    //     &[program_id.as_ref()],
-    //     &bpf_loader_upgradeable::id()
-    // );
-
-    // // Step 2: Verify passed program_data matches expected
-    // require!(
-    //     ctx.accounts.program_data.key() == expected_program_data,
-    //     ErrorCode::InvalidProgramData
-    // );
-
-    // // Step 3: Extract upgrade authority from program_data
-    // let program_data = ctx.accounts.program_data.try_borrow_data()?;
-
-    // // Program data structure:
-    // // Bytes 0-3: Account type
-    // // Bytes 4-12: Slot
-    // // Bytes 13-45: Upgrade Authority (32 bytes pubkey)
-    // // Bytes 46+: Executable code
-
-    // require!(
-    //     program_data.len() >= 45,
-    //     ErrorCode::InvalidUpgradeAuthority
-    // );
-
-    // let upgrade_authority_bytes = &program_data[13..45];
-    // let upgrade_authority = Pubkey::new_from_array(
-    //     upgrade_authority_bytes
-    //         .try_into()
-    //         .map_err(|_| ErrorCode::InvalidUpgradeAuthority)?
-    // );
-
-    // // Step 4: Verify caller is the upgrade authority
-    // require!(
-    //     ctx.accounts.user.key() == upgrade_authority,
-    //     ErrorCode::Unauthorized
-    // );
-
-    // Step 5: Set admin
+pub fn initialize_admin(
+    ctx: Context<InitializeAdmin>,
+    fund_id: u64,
+    admins: Vec<Pubkey>,
+    roles: Vec<u8>,
+    admin_threshold: u8,
+) -> Result<()> {
+    // Local validators load the program without a program data account,
+    // so the `local-testing` feature skips this check - never ship it
+    #[cfg(not(feature = "local-testing"))]
+    verify_upgrade_authority(
+        ctx.program_id,
+        &ctx.accounts.program_data,
+        &ctx.accounts.user.key(),
+    )?;
+
+    let fund = &mut ctx.accounts.fund;
+    fund.id = fund_id;
+    fund.creator = ctx.accounts.user.key();
+    fund.bump = ctx.bumps.fund;
+
+    // The founding council is given in full - the upgrade authority only gates
+    // creation, it doesn't get a seat on every fund's council
     let config = &mut ctx.accounts.config;
-    config.admins[0] = ctx.accounts.user.key();
-    config.admins[1] = admin2;
-    config.admins[2] = admin3;
+    config.fund = fund.key();
+    config.admins = admins;
+    config.roles = roles;
+    config.admin_threshold = admin_threshold;
+    config.admin_change_count = 0;
+    config.governance_mint = ctx.accounts.governance_mint.key();
+    config.params = GovernanceParams::DEFAULT;
+    config.emergency = EmergencyPolicy::DEFAULT;
+    config.paused = false;
     config.bump = ctx.bumps.config;
+    config.validate_council()?;
     
-    msg!("Admin initialized: {}", config.admins.len());
+    msg!("Admin initialized: {}-of-{}", config.admin_threshold, config.admins.len());
     
     Ok(())
 }
 
+/// Only the program's upgrade authority may create a fund
+#[cfg(not(feature = "local-testing"))]
+fn verify_upgrade_authority(
+    program_id: &Pubkey,
+    program_data: &AccountInfo,
+    user: &Pubkey,
+) -> Result<()> {
+    // program_data must be the account the loader derives for this program
+    let (expected_program_data, _bump) =
+        Pubkey::find_program_address(&[program_id.as_ref()], &bpf_loader_upgradeable::id());
+    require_keys_eq!(
+        program_data.key(),
+        expected_program_data,
+        ErrorCode::InvalidProgramData
+    );
+
+    let data = program_data.try_borrow_data()?;
+    let state = ProgramData::try_deserialize(&mut &data[..])
+        .map_err(|_| ErrorCode::InvalidUpgradeAuthority)?;
+
+    // A program that can no longer be upgraded has no one to hand admin rights to
+    require!(
+        state.upgrade_authority_address == Some(*user),
+        ErrorCode::Unauthorized
+    );
+    Ok(())
+}
+
+#[cfg(all(test, not(feature = "local-testing")))]
+mod tests {
+    use super::*;
+
+    /// Loader-serialized `UpgradeableLoaderState::ProgramData` header
+    fn program_data(upgrade_authority: Option<Pubkey>) -> Vec<u8> {
+        let mut data = 3u32.to_le_bytes().to_vec();
+        data.extend_from_slice(&0u64.to_le_bytes());
+        match upgrade_authority {
+            Some(authority) => {
+                data.push(1);
+                data.extend_from_slice(authority.as_ref());
+            }
+            None => data.push(0),
+        }
+        data
+    }
+
+    fn verify(key: Pubkey, mut data: Vec<u8>, user: &Pubkey, program_id: &Pubkey) -> Result<()> {
+        let owner = bpf_loader_upgradeable::id();
+        let mut lamports = 0;
+        let info = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &owner, false, 0);
+        verify_upgrade_authority(program_id, &info, user)
+    }
+
+    fn program_data_address(program_id: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[program_id.as_ref()], &bpf_loader_upgradeable::id()).0
+    }
+
+    #[test]
+    fn upgrade_authority_can_initialize() {
+        let program_id = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let key = program_data_address(&program_id);
+
+        verify(key, program_data(Some(authority)), &authority, &program_id).unwrap();
+    }
+
+    #[test]
+    fn other_signers_are_refused() {
+        let program_id = Pubkey::new_unique();
+        let key = program_data_address(&program_id);
+        let data = program_data(Some(Pubkey::new_unique()));
+
+        let result = verify(key, data, &Pubkey::new_unique(), &program_id);
+        assert_eq!(result.unwrap_err(), ErrorCode::Unauthorized.into());
+    }
+
+    #[test]
+    fn immutable_program_has_no_authority() {
+        let program_id = Pubkey::new_unique();
+        let key = program_data_address(&program_id);
+
+        let result = verify(key, program_data(None), &Pubkey::new_unique(), &program_id);
+        assert_eq!(result.unwrap_err(), ErrorCode::Unauthorized.into());
+    }
+
+    #[test]
+    fn program_data_of_another_program_is_refused() {
+        let program_id = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        // Valid program data, but derived for a program the attacker controls
+        let key = program_data_address(&Pubkey::new_unique());
+
+        let result = verify(key, program_data(Some(authority)), &authority, &program_id);
+        assert_eq!(result.unwrap_err(), ErrorCode::InvalidProgramData.into());
+    }
+
+    #[test]
+    fn malformed_program_data_is_refused() {
+        let program_id = Pubkey::new_unique();
+        let key = program_data_address(&program_id);
+
+        let result = verify(key, vec![0; 8], &Pubkey::new_unique(), &program_id);
+        assert_eq!(result.unwrap_err(), ErrorCode::InvalidUpgradeAuthority.into());
+    }
+}
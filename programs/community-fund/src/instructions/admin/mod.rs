@@ -1,6 +1,12 @@
 pub mod initialize;
-pub mod transfer;
+pub mod credits;
+pub mod params;
+pub mod council;
+pub mod handover;
 
 pub use initialize::*;
-pub use transfer::*;
+pub use credits::*;
+pub use params::*;
+pub use council::*;
+pub use handover::*;
 
@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use crate::state::{Config, Fund, GovernanceParams, TokenLedger, Vault};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct UpdateParams<'info> {
+    #[account(seeds = [b"fund", fund.id.to_be_bytes().as_ref()], bump = fund.bump)]
+    pub fund: Account<'info, Fund>,
+
+    #[account(
+        mut,
+        seeds = [b"config", fund.key().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn update_params(ctx: Context<UpdateParams>, params: GovernanceParams) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    let admin = ctx.accounts.admin.key();
+    config.require_role(&admin, Config::ROLE_PARAMS)?;
+
+    params.validate()?;
+
+    // Only proposals created from now on pick these up
+    config.params = params;
+
+    msg!(
+        "Params updated: voting_period={}, min_quorum={}, approval_bps={}, multisig_threshold={}",
+        params.voting_period,
+        params.min_quorum,
+        params.approval_threshold_bps,
+        params.multisig_threshold
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(round_id: u64, mint: Pubkey)]
+pub struct SetTokenMultisigThreshold<'info> {
+    #[account(seeds = [b"fund", fund.id.to_be_bytes().as_ref()], bump = fund.bump)]
+    pub fund: Account<'info, Fund>,
+
+    #[account(seeds = [b"config", fund.key().as_ref()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(seeds = [b"vault", fund.key().as_ref(), round_id.to_be_bytes().as_ref()], bump = vault.bump)]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [b"token_ledger", vault.key().as_ref(), mint.as_ref()],
+        bump = ledger.bump
+    )]
+    pub ledger: Account<'info, TokenLedger>,
+
+    pub admin: Signer<'info>,
+}
+
+/// Sets the multisig threshold of token grants paid in `mint` from the round's vault.
+/// Governance params are in lamports, so token grants are tiered by their mint's threshold.
+pub fn set_token_multisig_threshold(
+    ctx: Context<SetTokenMultisigThreshold>,
+    _round_id: u64,
+    mint: Pubkey,
+    threshold: u64,
+) -> Result<()> {
+    let admin = ctx.accounts.admin.key();
+    ctx.accounts.config.require_role(&admin, Config::ROLE_PARAMS)?;
+
+    let ledger = &mut ctx.accounts.ledger;
+    require!(
+        threshold <= ledger.max_multisig_threshold(),
+        ErrorCode::InvalidGovernanceParams
+    );
+    ledger.multisig_threshold = threshold;
+
+    msg!("Multisig threshold for {} set to {}", mint, threshold);
+    Ok(())
+}
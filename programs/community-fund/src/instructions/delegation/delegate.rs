@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use crate::state::{Delegation, Fund};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+#[instruction(delegate: Pubkey, category: u8)]
+pub struct DelegateVotes<'info> {
+    #[account(seeds = [b"fund", fund.id.to_be_bytes().as_ref()], bump = fund.bump)]
+    pub fund: Account<'info, Fund>,
+
+    #[account(
+        init,
+        seeds = [b"delegation", fund.key().as_ref(), delegator.key().as_ref(), &[category]],
+        bump,
+        payer = delegator,
+        space = 8 + Delegation::INIT_SPACE
+    )]
+    pub delegation: Account<'info, Delegation>,
+
+    #[account(mut)]
+    pub delegator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn delegate_votes(ctx: Context<DelegateVotes>, delegate: Pubkey, category: u8) -> Result<()> {
+    require!(
+        delegate != ctx.accounts.delegator.key(),
+        ErrorCode::InvalidDelegate
+    );
+
+    let delegation = &mut ctx.accounts.delegation;
+    delegation.fund = ctx.accounts.fund.key();
+    delegation.delegator = ctx.accounts.delegator.key();
+    delegation.delegate = delegate;
+    delegation.category = category;
+    delegation.created_at = Clock::get()?.unix_timestamp;
+    delegation.bump = ctx.bumps.delegation;
+
+    msg!("Votes delegated to {} (category {})", delegate, category);
+    Ok(())
+}
@@ -0,0 +1,5 @@
+pub mod delegate;
+pub mod revoke;
+
+pub use delegate::*;
+pub use revoke::*;
@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+use crate::state::{Delegation, Fund};
+
+#[derive(Accounts)]
+#[instruction(category: u8)]
+pub struct RevokeDelegation<'info> {
+    #[account(seeds = [b"fund", fund.id.to_be_bytes().as_ref()], bump = fund.bump)]
+    pub fund: Account<'info, Fund>,
+
+    #[account(
+        mut,
+        seeds = [b"delegation", fund.key().as_ref(), delegator.key().as_ref(), &[category]],
+        bump = delegation.bump,
+        has_one = delegator,
+        close = delegator
+    )]
+    pub delegation: Account<'info, Delegation>,
+
+    #[account(mut)]
+    pub delegator: Signer<'info>,
+}
+
+pub fn revoke_delegation(ctx: Context<RevokeDelegation>, _category: u8) -> Result<()> {
+    msg!("Delegation to {} revoked", ctx.accounts.delegation.delegate);
+    Ok(())
+}
@@ -0,0 +1,7 @@
+pub mod pause;
+pub mod policy;
+pub mod withdrawal;
+
+pub use pause::*;
+pub use policy::*;
+pub use withdrawal::*;
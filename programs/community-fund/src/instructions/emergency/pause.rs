@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+use crate::state::{Config, Fund};
+
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    #[account(seeds = [b"fund", fund.id.to_be_bytes().as_ref()], bump = fund.bump)]
+    pub fund: Account<'info, Fund>,
+
+    #[account(
+        mut,
+        seeds = [b"config", fund.key().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+}
+
+/// Emergency stop - while paused no funds leave a vault except by emergency withdrawal
+pub fn set_paused(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    let admin = ctx.accounts.admin.key();
+    config.require_role(&admin, Config::ROLE_PAUSER)?;
+
+    config.paused = paused;
+
+    msg!("Program {} by {}", if paused { "paused" } else { "unpaused" }, admin);
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use crate::state::{Config, EmergencyPolicy, Fund};
+
+#[derive(Accounts)]
+pub struct SetEmergencyPolicy<'info> {
+    #[account(seeds = [b"fund", fund.id.to_be_bytes().as_ref()], bump = fund.bump)]
+    pub fund: Account<'info, Fund>,
+
+    #[account(
+        mut,
+        seeds = [b"config", fund.key().as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn set_emergency_policy(ctx: Context<SetEmergencyPolicy>, policy: EmergencyPolicy) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    let admin = ctx.accounts.admin.key();
+    config.require_role(&admin, Config::ROLE_PARAMS)?;
+
+    policy.validate(config.role_count(Config::ROLE_TREASURER))?;
+
+    // Withdrawals already proposed keep the policy they were proposed under
+    config.emergency = policy;
+
+    msg!(
+        "Emergency policy updated: threshold={}, timelock={}",
+        policy.threshold,
+        policy.timelock
+    );
+    Ok(())
+}
@@ -0,0 +1,320 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{Config, EmergencyWithdrawal, Fund, TokenLedger, Vault};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+#[instruction(round_id: u64)]
+pub struct ProposeEmergencyWithdrawal<'info> {
+    #[account(seeds = [b"fund", fund.id.to_be_bytes().as_ref()], bump = fund.bump)]
+    pub fund: Account<'info, Fund>,
+
+    #[account(seeds = [b"config", fund.key().as_ref()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(seeds = [b"vault", fund.key().as_ref(), round_id.to_be_bytes().as_ref()], bump = vault.bump)]
+    pub vault: Account<'info, Vault>,
+
+    /// One pending withdrawal per vault
+    #[account(
+        init,
+        seeds = [b"emergency_withdrawal", vault.key().as_ref()],
+        bump,
+        payer = admin,
+        space = 8 + EmergencyWithdrawal::INIT_SPACE
+    )]
+    pub withdrawal: Account<'info, EmergencyWithdrawal>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Starts the timelock; the proposer's approval counts towards the threshold.
+/// With a `mint` the withdrawal moves that token out of the vault instead of lamports.
+pub fn propose_emergency_withdrawal(
+    ctx: Context<ProposeEmergencyWithdrawal>,
+    _round_id: u64,
+    amount: u64,
+    destination: Pubkey,
+    mint: Option<Pubkey>,
+) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let admin = ctx.accounts.admin.key();
+    config.require_role(&admin, Config::ROLE_TREASURER)?;
+    require!(config.paused, ErrorCode::ProgramNotPaused);
+    require!(amount > 0, ErrorCode::InvalidAmount);
+
+    let current_time = Clock::get()?.unix_timestamp;
+    let withdrawal = &mut ctx.accounts.withdrawal;
+    withdrawal.vault = ctx.accounts.vault.key();
+    withdrawal.proposer = admin;
+    withdrawal.destination = destination;
+    withdrawal.mint = mint;
+    withdrawal.amount = amount;
+    withdrawal.approvals = vec![admin];
+    withdrawal.threshold = config.emergency.threshold;
+    withdrawal.executable_at = current_time + config.emergency.timelock;
+    withdrawal.bump = ctx.bumps.withdrawal;
+
+    msg!(
+        "Emergency withdrawal of {} {} to {} proposed, executable at {}",
+        amount,
+        mint.map_or("lamports".to_string(), |mint| mint.to_string()),
+        destination,
+        withdrawal.executable_at
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(round_id: u64)]
+pub struct ApproveEmergencyWithdrawal<'info> {
+    #[account(seeds = [b"fund", fund.id.to_be_bytes().as_ref()], bump = fund.bump)]
+    pub fund: Account<'info, Fund>,
+
+    #[account(seeds = [b"config", fund.key().as_ref()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(seeds = [b"vault", fund.key().as_ref(), round_id.to_be_bytes().as_ref()], bump = vault.bump)]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [b"emergency_withdrawal", vault.key().as_ref()],
+        bump = withdrawal.bump
+    )]
+    pub withdrawal: Account<'info, EmergencyWithdrawal>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn approve_emergency_withdrawal(
+    ctx: Context<ApproveEmergencyWithdrawal>,
+    _round_id: u64,
+) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let admin = ctx.accounts.admin.key();
+    config.require_role(&admin, Config::ROLE_TREASURER)?;
+    require!(config.paused, ErrorCode::ProgramNotPaused);
+
+    let withdrawal = &mut ctx.accounts.withdrawal;
+    require!(
+        !withdrawal.approvals.contains(&admin),
+        ErrorCode::AlreadyApproved
+    );
+    // Drop approvals from former treasurers so the list stays within MAX_ADMINS
+    withdrawal
+        .approvals
+        .retain(|a| config.has_role(a, Config::ROLE_TREASURER));
+    withdrawal.approvals.push(admin);
+
+    msg!(
+        "Emergency withdrawal approved: {} of {}",
+        withdrawal.approvals.len(),
+        withdrawal.threshold
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(round_id: u64)]
+pub struct ExecuteEmergencyWithdrawal<'info> {
+    #[account(seeds = [b"fund", fund.id.to_be_bytes().as_ref()], bump = fund.bump)]
+    pub fund: Account<'info, Fund>,
+
+    #[account(seeds = [b"config", fund.key().as_ref()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut, seeds = [b"vault", fund.key().as_ref(), round_id.to_be_bytes().as_ref()], bump = vault.bump)]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [b"emergency_withdrawal", vault.key().as_ref()],
+        bump = withdrawal.bump,
+        has_one = proposer,
+        has_one = destination,
+        close = proposer
+    )]
+    pub withdrawal: Account<'info, EmergencyWithdrawal>,
+
+    /// CHECK: Receives the withdrawal's rent back, address checked by has_one
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+
+    /// CHECK: Receives the funds, address checked by has_one
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+
+    /// Token being withdrawn - token withdrawals only
+    #[account(constraint = withdrawal.mint == Some(mint.key()) @ ErrorCode::InvalidRequestedMint)]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Vault's token account for the withdrawn mint
+    #[account(
+        mut,
+        seeds = [b"vault_token", vault.key().as_ref(), withdrawal.mint.unwrap_or_default().as_ref()],
+        bump
+    )]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Destination's token account for the withdrawn mint
+    #[account(
+        mut,
+        constraint = withdrawal.mint == Some(destination_token_account.mint) @ ErrorCode::InvalidRequestedMint,
+        constraint = destination_token_account.owner == destination.key() @ ErrorCode::InvalidTokenOwner
+    )]
+    pub destination_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token ledger of the withdrawn mint, which records the withdrawal
+    #[account(
+        mut,
+        seeds = [b"token_ledger", vault.key().as_ref(), withdrawal.mint.unwrap_or_default().as_ref()],
+        bump = ledger.bump
+    )]
+    pub ledger: Option<Account<'info, TokenLedger>>,
+
+    /// Legacy Token program or Token-2022
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+/// Permissionless once approved and the timelock has run out
+pub fn execute_emergency_withdrawal(
+    ctx: Context<ExecuteEmergencyWithdrawal>,
+    round_id: u64,
+) -> Result<()> {
+    require!(ctx.accounts.config.paused, ErrorCode::ProgramNotPaused);
+
+    // Approvals from admins who have since lost the treasurer role don't count
+    let withdrawal = &ctx.accounts.withdrawal;
+    let approved = ctx
+        .accounts
+        .config
+        .count_role_approvals(&withdrawal.approvals, Config::ROLE_TREASURER);
+    require!(
+        approved >= withdrawal.threshold as usize,
+        ErrorCode::NotEnoughApprovals
+    );
+    let current_time = Clock::get()?.unix_timestamp;
+    require!(
+        current_time >= withdrawal.executable_at,
+        ErrorCode::TimelockNotElapsed
+    );
+
+    let amount = withdrawal.amount;
+    if withdrawal.mint.is_some() {
+        return withdraw_tokens(ctx.accounts, round_id, amount);
+    }
+
+    // The vault keeps its rent-exempt reserve
+    let vault_info = ctx.accounts.vault.to_account_info();
+    require!(
+        Vault::spendable_lamports(&vault_info)? >= amount,
+        ErrorCode::InsufficientVaultBalance
+    );
+
+    **vault_info.try_borrow_mut_lamports()? -= amount;
+    **ctx.accounts.destination.to_account_info().try_borrow_mut_lamports()? += amount;
+
+    let vault = &mut ctx.accounts.vault;
+    vault.emergency_withdrawn += amount;
+
+    msg!("Emergency withdrawal of {} lamports executed", amount);
+    Ok(())
+}
+
+/// Moves `amount` of the withdrawal's mint out of the vault's token account, signed by the vault PDA,
+/// and records it on the mint's ledger
+fn withdraw_tokens(
+    accounts: &mut ExecuteEmergencyWithdrawal,
+    round_id: u64,
+    amount: u64,
+) -> Result<()> {
+    let (
+        Some(mint),
+        Some(vault_token_account),
+        Some(destination_token_account),
+        Some(ledger),
+        Some(token_program),
+    ) = (
+        &accounts.mint,
+        &accounts.vault_token_account,
+        &accounts.destination_token_account,
+        &mut accounts.ledger,
+        &accounts.token_program,
+    ) else {
+        return err!(ErrorCode::TokenAccountsRequired);
+    };
+    require!(
+        vault_token_account.amount >= amount,
+        ErrorCode::InsufficientVaultBalance
+    );
+
+    let fund_key = accounts.fund.key();
+    let round_id = round_id.to_be_bytes();
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"vault",
+        fund_key.as_ref(),
+        round_id.as_ref(),
+        &[accounts.vault.bump],
+    ]];
+    let cpi_context = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        TransferChecked {
+            from: vault_token_account.to_account_info(),
+            mint: mint.to_account_info(),
+            to: destination_token_account.to_account_info(),
+            authority: accounts.vault.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token_interface::transfer_checked(cpi_context, amount, mint.decimals)?;
+    ledger.record_emergency_withdrawal(amount, vault_token_account.amount - amount);
+
+    msg!("Emergency withdrawal of {} tokens of {} executed", amount, mint.key());
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(round_id: u64)]
+pub struct CancelEmergencyWithdrawal<'info> {
+    #[account(seeds = [b"fund", fund.id.to_be_bytes().as_ref()], bump = fund.bump)]
+    pub fund: Account<'info, Fund>,
+
+    #[account(seeds = [b"config", fund.key().as_ref()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(seeds = [b"vault", fund.key().as_ref(), round_id.to_be_bytes().as_ref()], bump = vault.bump)]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [b"emergency_withdrawal", vault.key().as_ref()],
+        bump = withdrawal.bump,
+        has_one = proposer,
+        close = proposer
+    )]
+    pub withdrawal: Account<'info, EmergencyWithdrawal>,
+
+    /// CHECK: Receives the withdrawal's rent back, address checked by has_one
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+
+    pub admin: Signer<'info>,
+}
+
+/// Treasurers and pausers can drop a pending withdrawal, e.g. once the emergency is over
+pub fn cancel_emergency_withdrawal(
+    ctx: Context<CancelEmergencyWithdrawal>,
+    _round_id: u64,
+) -> Result<()> {
+    let admin = ctx.accounts.admin.key();
+    ctx.accounts
+        .config
+        .require_any_role(&admin, Config::ROLE_TREASURER | Config::ROLE_PAUSER)?;
+
+    msg!("Emergency withdrawal cancelled by {}", admin);
+    Ok(())
+}
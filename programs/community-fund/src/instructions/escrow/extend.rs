@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use crate::state::{Fund, VoterLock};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct ExtendLock<'info> {
+    #[account(seeds = [b"fund", fund.id.to_be_bytes().as_ref()], bump = fund.bump)]
+    pub fund: Account<'info, Fund>,
+
+    #[account(
+        mut,
+        seeds = [b"voter_lock", fund.key().as_ref(), user.key().as_ref()],
+        bump = voter_lock.bump
+    )]
+    pub voter_lock: Account<'info, VoterLock>,
+
+    pub user: Signer<'info>,
+}
+
+pub fn extend_lock(ctx: Context<ExtendLock>, lock_duration: i64) -> Result<()> {
+    require!(
+        (VoterLock::MIN_LOCK_DURATION..=VoterLock::MAX_LOCK_DURATION).contains(&lock_duration),
+        ErrorCode::InvalidLockDuration
+    );
+
+    let voter_lock = &mut ctx.accounts.voter_lock;
+    require!(voter_lock.amount > 0, ErrorCode::InvalidLockAmount);
+
+    // New end is measured from now and must push the lock further out
+    let lock_end = Clock::get()?.unix_timestamp + lock_duration;
+    require!(lock_end > voter_lock.lock_end, ErrorCode::InvalidLockDuration);
+
+    voter_lock.lock_end = lock_end;
+
+    msg!("Lock extended until {}", lock_end);
+    Ok(())
+}
@@ -0,0 +1,101 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{Config, Fund, VoterLock};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct LockTokens<'info> {
+    #[account(seeds = [b"fund", fund.id.to_be_bytes().as_ref()], bump = fund.bump)]
+    pub fund: Account<'info, Fund>,
+
+    #[account(
+        init_if_needed,
+        seeds = [b"voter_lock", fund.key().as_ref(), user.key().as_ref()],
+        bump,
+        payer = user,
+        space = 8 + VoterLock::INIT_SPACE
+    )]
+    pub voter_lock: Account<'info, VoterLock>,
+
+    /// Program-owned escrow - only the voter_lock PDA can move these tokens
+    #[account(
+        init_if_needed,
+        seeds = [b"lock_escrow", fund.key().as_ref(), user.key().as_ref()],
+        bump,
+        payer = user,
+        token::mint = governance_mint,
+        token::authority = voter_lock,
+        token::token_program = token_program
+    )]
+    pub escrow_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = user_token_account.mint == config.governance_mint @ ErrorCode::InvalidGovernanceMint,
+        constraint = user_token_account.owner == user.key() @ ErrorCode::InvalidTokenOwner
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        address = config.governance_mint @ ErrorCode::InvalidGovernanceMint,
+        mint::token_program = token_program
+    )]
+    pub governance_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(seeds = [b"config", fund.key().as_ref()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn lock_tokens(ctx: Context<LockTokens>, amount: u64, lock_duration: i64) -> Result<()> {
+    require!(amount > 0, ErrorCode::InvalidLockAmount);
+    require!(
+        (VoterLock::MIN_LOCK_DURATION..=VoterLock::MAX_LOCK_DURATION).contains(&lock_duration),
+        ErrorCode::InvalidLockDuration
+    );
+
+    let current_time = Clock::get()?.unix_timestamp;
+    let lock_end = current_time + lock_duration;
+
+    // Topping up an existing lock can never shorten it
+    require!(
+        lock_end >= ctx.accounts.voter_lock.lock_end,
+        ErrorCode::InvalidLockDuration
+    );
+
+    // Move tokens from the voter into escrow
+    let balance_before = ctx.accounts.escrow_token_account.amount;
+    let cpi_context = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.user_token_account.to_account_info(),
+            mint: ctx.accounts.governance_mint.to_account_info(),
+            to: ctx.accounts.escrow_token_account.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        },
+    );
+    token_interface::transfer_checked(cpi_context, amount, ctx.accounts.governance_mint.decimals)?;
+
+    // Weight follows what reached escrow, net of any transfer fee
+    ctx.accounts.escrow_token_account.reload()?;
+    let received = ctx.accounts.escrow_token_account.amount - balance_before;
+    let voter_lock = &mut ctx.accounts.voter_lock;
+
+    if voter_lock.amount == 0 {
+        voter_lock.fund = ctx.accounts.fund.key();
+        voter_lock.owner = ctx.accounts.user.key();
+        voter_lock.lock_start = current_time;
+        voter_lock.bump = ctx.bumps.voter_lock;
+        voter_lock.escrow_bump = ctx.bumps.escrow_token_account;
+    }
+    voter_lock.amount += received;
+    voter_lock.lock_end = lock_end;
+
+    msg!("Locked {} tokens until {}", voter_lock.amount, lock_end);
+    Ok(())
+}
@@ -0,0 +1,7 @@
+pub mod lock;
+pub mod extend;
+pub mod unlock;
+
+pub use lock::*;
+pub use extend::*;
+pub use unlock::*;
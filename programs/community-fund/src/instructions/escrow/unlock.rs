@@ -0,0 +1,122 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{transfer_fee::TransferFeeAmount, BaseStateWithExtensions, StateWithExtensions},
+};
+use anchor_spl::token_interface::{
+    self, CloseAccount, HarvestWithheldTokensToMint, Mint, TokenAccount, TokenInterface,
+    TransferChecked,
+};
+use crate::state::{Fund, VoterLock};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+pub struct UnlockTokens<'info> {
+    #[account(seeds = [b"fund", fund.id.to_be_bytes().as_ref()], bump = fund.bump)]
+    pub fund: Account<'info, Fund>,
+
+    #[account(
+        mut,
+        seeds = [b"voter_lock", fund.key().as_ref(), user.key().as_ref()],
+        bump = voter_lock.bump,
+        close = user
+    )]
+    pub voter_lock: Account<'info, VoterLock>,
+
+    #[account(
+        mut,
+        seeds = [b"lock_escrow", fund.key().as_ref(), user.key().as_ref()],
+        bump = voter_lock.escrow_bump
+    )]
+    pub escrow_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = user_token_account.mint == escrow_token_account.mint @ ErrorCode::InvalidGovernanceMint,
+        constraint = user_token_account.owner == user.key() @ ErrorCode::InvalidTokenOwner
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Mutable so transfer fees withheld in escrow can be harvested to it
+    #[account(
+        mut,
+        address = escrow_token_account.mint @ ErrorCode::InvalidGovernanceMint,
+        mint::token_program = token_program
+    )]
+    pub governance_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn unlock_tokens(ctx: Context<UnlockTokens>) -> Result<()> {
+    let voter_lock = &ctx.accounts.voter_lock;
+    let current_time = Clock::get()?.unix_timestamp;
+    require!(current_time >= voter_lock.lock_end, ErrorCode::LockNotExpired);
+
+    let fund_key = ctx.accounts.fund.key();
+    let user_key = ctx.accounts.user.key();
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"voter_lock",
+        fund_key.as_ref(),
+        user_key.as_ref(),
+        &[voter_lock.bump],
+    ]];
+
+    // Return every escrowed token to the voter
+    let amount = ctx.accounts.escrow_token_account.amount;
+    let cpi_context = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.escrow_token_account.to_account_info(),
+            mint: ctx.accounts.governance_mint.to_account_info(),
+            to: ctx.accounts.user_token_account.to_account_info(),
+            authority: voter_lock.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token_interface::transfer_checked(cpi_context, amount, ctx.accounts.governance_mint.decimals)?;
+
+    // Token-2022 refuses to close an account still holding withheld transfer fees
+    let escrow_info = ctx.accounts.escrow_token_account.to_account_info();
+    if withheld_transfer_fees(&escrow_info)? > 0 {
+        let cpi_context = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            HarvestWithheldTokensToMint {
+                token_program_id: ctx.accounts.token_program.to_account_info(),
+                mint: ctx.accounts.governance_mint.to_account_info(),
+            },
+        );
+        token_interface::harvest_withheld_tokens_to_mint(cpi_context, vec![escrow_info])?;
+    }
+
+    // Close the empty escrow; the lock account itself is closed by Anchor
+    let cpi_context = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.escrow_token_account.to_account_info(),
+            destination: ctx.accounts.user.to_account_info(),
+            authority: voter_lock.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token_interface::close_account(cpi_context)?;
+
+    msg!("Unlocked {} tokens", amount);
+    Ok(())
+}
+
+/// Transfer fees withheld in a Token-2022 account; always 0 for legacy token accounts
+fn withheld_transfer_fees(token_account: &AccountInfo) -> Result<u64> {
+    if *token_account.owner != spl_token_2022::ID {
+        return Ok(0);
+    }
+    let data = token_account.try_borrow_data()?;
+    let state = StateWithExtensions::<spl_token_2022::state::Account>::unpack(&data)?;
+    Ok(state
+        .get_extension::<TransferFeeAmount>()
+        .map(|fee| u64::from(fee.withheld_amount))
+        .unwrap_or(0))
+}
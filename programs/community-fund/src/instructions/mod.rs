@@ -1,8 +1,16 @@
 pub mod admin;
+pub mod delegation;
+pub mod emergency;
+pub mod escrow;
 pub mod proposal;
+pub mod round;
 pub mod vault;
 
 pub use admin::*;
+pub use delegation::*;
+pub use emergency::*;
+pub use escrow::*;
 pub use proposal::*;
+pub use round::*;
 pub use vault::*;
 
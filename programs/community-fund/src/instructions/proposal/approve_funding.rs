@@ -1,22 +1,49 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
 use crate::errors::ErrorCode;
-use crate::state::{Config, Proposal, ProposalStatus};
+use crate::state::{Config, Fund, Proposal, ProposalStatus, TokenLedger, Vault};
 
 
 #[derive(Accounts)]
 #[instruction(proposal_id: u64, owner: Pubkey)]
 pub struct ApproveFunding<'info> {
+    #[account(seeds = [b"fund", fund.id.to_be_bytes().as_ref()], bump = fund.bump)]
+    pub fund: Account<'info, Fund>,
+
     #[account(
         mut,
-        seeds = [b"proposal", owner.as_ref(), proposal_id.to_be_bytes().as_ref()],
+        seeds = [b"proposal", fund.key().as_ref(), owner.as_ref(), proposal_id.to_be_bytes().as_ref()],
         bump = proposal.bump
     )]
     pub proposal: Account<'info, Proposal>,
 
+    /// Vault that reserves the grant once it is approved
+    #[account(
+        mut,
+        seeds = [b"vault", fund.key().as_ref(), proposal.round_id.to_be_bytes().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    /// Token ledger that reserves token grants - required when the proposal requests a mint
+    #[account(
+        mut,
+        seeds = [b"token_ledger", vault.key().as_ref(), proposal.requested_mint.unwrap_or_default().as_ref()],
+        bump = ledger.bump
+    )]
+    pub ledger: Option<Account<'info, TokenLedger>>,
+
+    /// Vault's token account for the requested mint, whose balance backs the reservation
+    #[account(
+        seeds = [b"vault_token", vault.key().as_ref(), proposal.requested_mint.unwrap_or_default().as_ref()],
+        bump
+    )]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
     #[account(mut)]
     pub admin: Signer<'info>,
 
-    #[account(seeds = [b"config"], bump = config.bump)]
+    #[account(seeds = [b"config", fund.key().as_ref()], bump = config.bump)]
     pub config: Account<'info, Config>,
 }
 
@@ -30,36 +57,234 @@ pub fn approve_funding(
     let config = &ctx.accounts.config;
 
     // Step 1: Verify admin is valid
-    require!(config.admins.contains(&admin), ErrorCode::Unauthorized);
+    config.require_role(&admin, Config::ROLE_TREASURER)?;
 
-    // Step 2: Check amount threshold - 1000 SOL = 1000 * 10^9 lamports
-    const MULTISIG_THRESHOLD: u64 = 1_000_000_000_000; // 1000 SOL in lamports
+    // Only proposals that passed the vote can collect approvals
+    require!(
+        proposal.status.can_transition_to(ProposalStatus::Approved),
+        ErrorCode::InvalidStatusTransition
+    );
+
+    // Step 2: Check amount threshold - the one in force when the proposal was created
+    // for SOL grants, the mint's own threshold for token grants
+    let amount = proposal.amount_requested;
+    let multisig_threshold = proposal.multisig_threshold(ctx.accounts.ledger.as_deref())?;
+    if record_tiered_approval(&mut proposal.funding_approvals, admin, amount, multisig_threshold, config)? {
+        proposal.transition_to(ProposalStatus::Approved)?;
+
+        // Reserve the grant so approvals can't promise more than the vault holds
+        let commitment = proposal.outstanding_commitment();
+        let committed = match proposal.requested_mint {
+            None => {
+                let vault = &mut ctx.accounts.vault;
+                let balance = Vault::spendable_lamports(&vault.to_account_info())?;
+                vault.commit(commitment, balance)
+            }
+            Some(_) => {
+                let (Some(ledger), Some(vault_token_account)) =
+                    (&mut ctx.accounts.ledger, &ctx.accounts.vault_token_account)
+                else {
+                    return err!(ErrorCode::TokenLedgerRequired);
+                };
+                ledger.commit(commitment, vault_token_account.amount)
+            }
+        };
+        require!(committed, ErrorCode::InsufficientFreeBalance);
+    }
+
+    Ok(())
+}
+
+/// Records one admin's approval of `amount`.
+/// Below the threshold a single admin approves directly; at or above it the
+/// council's admin threshold is needed.
+/// Returns true once the amount is approved.
+pub(crate) fn record_tiered_approval(
+    approvals: &mut Vec<Pubkey>,
+    admin: Pubkey,
+    amount: u64,
+    multisig_threshold: u64,
+    config: &Config,
+) -> Result<bool> {
+    if amount >= multisig_threshold {
+        // Large amount: Need N-of-M multisig
 
-    if proposal.amount_requested >= MULTISIG_THRESHOLD {
-        // Large amount: Need 2-of-3 multisig
-        
         // Check duplicate
-        require!(
-            !proposal.funding_approvals.contains(&admin),
-            ErrorCode::AlreadyApproved
-        );
-        
+        require!(!approvals.contains(&admin), ErrorCode::AlreadyApproved);
+
+        // Drop approvals from former treasurers so the list stays within MAX_ADMINS
+        approvals.retain(|a| config.has_role(a, Config::ROLE_TREASURER));
+
         // Add approval
-        proposal.funding_approvals.push(admin);
-        
-        // Check if threshold met
-        if proposal.funding_approvals.len() >= 2 {
-            proposal.status = ProposalStatus::Approved;
-            msg!("Proposal approved with 2-of-3 multisig");
+        approvals.push(admin);
+
+        // Check if threshold met - approvals from former treasurers no longer count
+        let approved = config.count_role_approvals(approvals, Config::ROLE_TREASURER);
+        let needed = config.admin_threshold as usize;
+        if approved >= needed {
+            msg!(
+                "Approved with {}-of-{} multisig",
+                needed,
+                config.role_count(Config::ROLE_TREASURER)
+            );
+            Ok(true)
         } else {
-            msg!("1 of 2 approvals received");
+            msg!("{} of {} approvals received", approved, needed);
+            Ok(false)
         }
-        
     } else {
         // Small amount: Single admin can approve directly
-        proposal.status = ProposalStatus::Approved;
         msg!("Small amount - approved by single admin");
+        Ok(true)
     }
-    
-    Ok(())
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{EmergencyPolicy, GovernanceParams, MAX_ADMINS};
+    use crate::state::VotingMode;
+
+    const THRESHOLD: u64 = 1_000;
+
+    fn council(admin_count: usize, admin_threshold: u8) -> Config {
+        Config {
+            fund: Pubkey::new_unique(),
+            admins: (0..admin_count).map(|_| Pubkey::new_unique()).collect(),
+            roles: vec![Config::ALL_ROLES; admin_count],
+            admin_threshold,
+            admin_change_count: 0,
+            governance_mint: Pubkey::new_unique(),
+            params: GovernanceParams::DEFAULT,
+            emergency: EmergencyPolicy::DEFAULT,
+            paused: false,
+            bump: 0,
+        }
+    }
+
+    /// Approval of a request at the multisig threshold by admin `index`
+    fn approve_large(approvals: &mut Vec<Pubkey>, config: &Config, index: usize) -> Result<bool> {
+        record_tiered_approval(approvals, config.admins[index], THRESHOLD, THRESHOLD, config)
+    }
+
+    #[test]
+    fn single_admin_approves_small_amount() {
+        let config = council(3, 2);
+        let mut approvals = Vec::new();
+
+        let approved =
+            record_tiered_approval(&mut approvals, config.admins[0], THRESHOLD - 1, THRESHOLD, &config);
+        assert!(approved.unwrap());
+        assert!(approvals.is_empty());
+    }
+
+    #[test]
+    fn large_amount_needs_admin_threshold() {
+        let config = council(3, 2);
+        let mut approvals = Vec::new();
+
+        assert!(!approve_large(&mut approvals, &config, 0).unwrap());
+        assert!(approve_large(&mut approvals, &config, 1).unwrap());
+        assert_eq!(approvals, vec![config.admins[0], config.admins[1]]);
+    }
+
+    #[test]
+    fn threshold_follows_the_council() {
+        let config = council(4, 3);
+        let mut approvals = Vec::new();
+
+        assert!(!approve_large(&mut approvals, &config, 0).unwrap());
+        assert!(!approve_large(&mut approvals, &config, 1).unwrap());
+        assert!(approve_large(&mut approvals, &config, 2).unwrap());
+    }
+
+    #[test]
+    fn same_admin_cannot_approve_twice() {
+        let config = council(3, 2);
+        let mut approvals = Vec::new();
+
+        approve_large(&mut approvals, &config, 0).unwrap();
+        let result = approve_large(&mut approvals, &config, 0);
+        assert_eq!(result.unwrap_err(), ErrorCode::AlreadyApproved.into());
+        assert_eq!(approvals.len(), 1);
+    }
+
+    #[test]
+    fn approvals_from_non_treasurers_do_not_count() {
+        let mut config = council(3, 2);
+        let mut approvals = Vec::new();
+
+        approve_large(&mut approvals, &config, 0).unwrap();
+
+        // Admin 0 loses the treasurer role before the second approval arrives
+        config.roles[0] = Config::ROLE_REVIEWER;
+        assert!(!approve_large(&mut approvals, &config, 1).unwrap());
+        assert!(approve_large(&mut approvals, &config, 2).unwrap());
+    }
+
+    #[test]
+    fn stale_approvals_are_dropped_before_recording() {
+        let config = council(3, 2);
+        // A full list left behind by admins who have since been replaced
+        let mut approvals: Vec<Pubkey> = (0..MAX_ADMINS).map(|_| Pubkey::new_unique()).collect();
+
+        assert!(!approve_large(&mut approvals, &config, 0).unwrap());
+        assert_eq!(approvals, vec![config.admins[0]]);
+    }
+
+    #[test]
+    fn token_grants_are_tiered_by_their_mint_threshold() {
+        let config = council(3, 2);
+        let ledger = TokenLedger {
+            mint: Pubkey::new_unique(),
+            total_deposited: 100 * THRESHOLD,
+            total_claimed: 0,
+            emergency_withdrawn: 0,
+            committed: 0,
+            multisig_threshold: THRESHOLD,
+            bump: 0,
+            token_account_bump: 0,
+        };
+        let threshold = token_grant(&ledger).multisig_threshold(Some(&ledger)).unwrap();
+        assert_eq!(threshold, THRESHOLD);
+
+        // Just below the mint's threshold one treasurer is enough
+        let mut approvals = Vec::new();
+        let approved =
+            record_tiered_approval(&mut approvals, config.admins[0], THRESHOLD - 1, threshold, &config);
+        assert!(approved.unwrap());
+
+        // At the threshold the council has to sign off, though it's far below the lamport one
+        assert!(!approve_large(&mut approvals, &config, 0).unwrap());
+        assert!(approve_large(&mut approvals, &config, 1).unwrap());
+    }
+
+    fn token_grant(ledger: &TokenLedger) -> Proposal {
+        Proposal {
+            fund: Pubkey::new_unique(),
+            id: 0,
+            owner: Pubkey::new_unique(),
+            title: String::new(),
+            description: String::new(),
+            amount_requested: THRESHOLD,
+            category: 0,
+            voting_mode: VotingMode::TokenWeighted,
+            status: ProposalStatus::Finalized,
+            created_at: 0,
+            yes_votes: 0,
+            no_votes: 0,
+            abstain_votes: 0,
+            bump: 0,
+            funding_approvals: Vec::new(),
+            finalized_at: 0,
+            params: GovernanceParams::DEFAULT,
+            milestones: Vec::new(),
+            vesting: None,
+            vesting_revoked_at: 0,
+            amount_claimed: 0,
+            requested_mint: Some(ledger.mint),
+            round_id: 0,
+            voting_end: 0,
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use crate::state::{Fund, Proposal, ProposalStatus, Round, TokenLedger, Vault};
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct CancelProposal<'info> {
+    #[account(seeds = [b"fund", fund.id.to_be_bytes().as_ref()], bump = fund.bump)]
+    pub fund: Account<'info, Fund>,
+
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal", fund.key().as_ref(), owner.key().as_ref(), proposal_id.to_be_bytes().as_ref()],
+        bump = proposal.bump,
+        has_one = owner
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// Round whose budget is released if the proposal had passed
+    #[account(
+        mut,
+        seeds = [b"round", fund.key().as_ref(), proposal.round_id.to_be_bytes().as_ref()],
+        bump = round.bump
+    )]
+    pub round: Account<'info, Round>,
+
+    /// Vault whose reservation is released if the proposal was approved
+    #[account(
+        mut,
+        seeds = [b"vault", fund.key().as_ref(), proposal.round_id.to_be_bytes().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    /// Token ledger of the requested mint - required to release an approved token grant
+    #[account(
+        mut,
+        seeds = [b"token_ledger", vault.key().as_ref(), proposal.requested_mint.unwrap_or_default().as_ref()],
+        bump = ledger.bump
+    )]
+    pub ledger: Option<Account<'info, TokenLedger>>,
+}
+
+/// Owner withdraws their proposal any time before the funds are claimed
+pub fn cancel_proposal(ctx: Context<CancelProposal>, _proposal_id: u64) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    let released = proposal.outstanding_allocation();
+    let uncommitted = proposal.outstanding_commitment();
+    proposal.transition_to(ProposalStatus::Cancelled)?;
+    ctx.accounts.round.release(released);
+    ctx.accounts.vault.release_grant(
+        ctx.accounts.ledger.as_deref_mut(),
+        proposal.requested_mint,
+        uncommitted,
+    )?;
+
+    msg!("Proposal {} cancelled", proposal.id);
+    Ok(())
+}
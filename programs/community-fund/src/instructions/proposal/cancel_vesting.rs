@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+use crate::state::{Config, Fund, Proposal, ProposalStatus, Round, TokenLedger, Vault};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u64, owner: Pubkey)]
+pub struct CancelVesting<'info> {
+    #[account(seeds = [b"fund", fund.id.to_be_bytes().as_ref()], bump = fund.bump)]
+    pub fund: Account<'info, Fund>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal", fund.key().as_ref(), owner.as_ref(), proposal_id.to_be_bytes().as_ref()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// Round whose budget is released for the unvested remainder
+    #[account(
+        mut,
+        seeds = [b"round", fund.key().as_ref(), proposal.round_id.to_be_bytes().as_ref()],
+        bump = round.bump
+    )]
+    pub round: Account<'info, Round>,
+
+    /// Vault whose reservation shrinks to what has vested
+    #[account(
+        mut,
+        seeds = [b"vault", fund.key().as_ref(), proposal.round_id.to_be_bytes().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    /// Token ledger whose reservation shrinks instead, for token grants
+    #[account(
+        mut,
+        seeds = [b"token_ledger", vault.key().as_ref(), proposal.requested_mint.unwrap_or_default().as_ref()],
+        bump = ledger.bump
+    )]
+    pub ledger: Option<Account<'info, TokenLedger>>,
+
+    pub admin: Signer<'info>,
+
+    #[account(seeds = [b"config", fund.key().as_ref()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+}
+
+/// Stops vesting now. What has vested stays claimable; the unvested
+/// remainder never leaves the vault and is free for other grants.
+pub fn cancel_vesting(
+    ctx: Context<CancelVesting>,
+    _proposal_id: u64,
+    _owner: Pubkey,
+) -> Result<()> {
+    let admin = ctx.accounts.admin.key();
+    ctx.accounts.config.require_role(&admin, Config::ROLE_TREASURER)?;
+
+    let proposal = &mut ctx.accounts.proposal;
+    require!(
+        proposal.status == ProposalStatus::Approved,
+        ErrorCode::NotApproved
+    );
+    require!(proposal.vesting.is_some(), ErrorCode::InvalidPayoutMode);
+    require!(
+        proposal.vesting_revoked_at == 0,
+        ErrorCode::VestingAlreadyCancelled
+    );
+
+    let current_time = Clock::get()?.unix_timestamp;
+    let unvested = proposal.revoke_vesting(current_time)?;
+
+    // The unvested remainder goes back to the round's budget and the vault's free balance
+    ctx.accounts.round.release(unvested);
+    ctx.accounts.vault.release_grant(
+        ctx.accounts.ledger.as_deref_mut(),
+        proposal.requested_mint,
+        unvested,
+    )?;
+
+    msg!(
+        "Vesting cancelled: {} of {} vested, {} returned to the vault",
+        proposal.amount_requested - unvested,
+        proposal.amount_requested,
+        unvested
+    );
+    Ok(())
+}
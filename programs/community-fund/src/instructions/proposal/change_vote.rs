@@ -0,0 +1,55 @@
+use crate::errors::ErrorCode;
+use crate::state::{Fund, Proposal, ProposalStatus, VoteAccount, VoteChoice};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u64, owner: Pubkey)]
+pub struct ChangeVote<'info> {
+    #[account(seeds = [b"fund", fund.id.to_be_bytes().as_ref()], bump = fund.bump)]
+    pub fund: Account<'info, Fund>,
+
+    #[account(
+        mut,
+        seeds = [b"vote", proposal.key().as_ref(), user.key().as_ref()],
+        bump = vote_account.bump
+    )]
+    pub vote_account: Account<'info, VoteAccount>,
+
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal", fund.key().as_ref(), owner.as_ref(), proposal_id.to_be_bytes().as_ref()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
+pub fn change_vote(
+    ctx: Context<ChangeVote>,
+    _proposal_id: u64,
+    _owner: Pubkey,
+    new_choice: VoteChoice,
+) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+    let proposal = &mut ctx.accounts.proposal;
+    require!(
+        current_time < proposal.voting_end,
+        ErrorCode::VotingExpired
+    );
+    require!(
+        proposal.status == ProposalStatus::Pending,
+        ErrorCode::ProposalNotActive
+    );
+
+    // Move the recorded weight from the old choice to the new one
+    let vote = &mut ctx.accounts.vote_account;
+    proposal.remove_vote(vote.choice, vote.token_weight);
+    proposal.add_vote(new_choice, vote.token_weight);
+
+    vote.choice = new_choice;
+    vote.timestamp = current_time;
+
+    msg!("Vote changed, weight {}", vote.token_weight);
+    Ok(())
+}
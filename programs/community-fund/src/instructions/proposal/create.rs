@@ -1,15 +1,27 @@
 use anchor_lang::prelude::*;
-use crate::state::{Proposal, ProposalStatus, UserProfile};
+use crate::state::{
+    Config, Delegation, Fund, Milestone, MilestoneInput, MilestoneStatus, Proposal,
+    ProposalStatus, Round, UserProfile, VestingSchedule,
+};
+use crate::errors::ErrorCode;
 
 #[derive(Accounts)]
 pub struct CreateProposal<'info> {
-    #[account(mut)]
+    #[account(seeds = [b"fund", fund.id.to_be_bytes().as_ref()], bump = fund.bump)]
+    pub fund: Account<'info, Fund>,
+
+    #[account(
+        mut,
+        seeds = [b"user_profile", fund.key().as_ref(), user.key().as_ref()],
+        bump = user_profile.bump
+    )]
     pub user_profile: Account<'info, UserProfile>,
 
     #[account(
         init,
         seeds = [
             b"proposal",
+            fund.key().as_ref(),
             user.key().as_ref(),
             user_profile.proposal_count.to_be_bytes().as_ref()
         ],
@@ -19,6 +31,13 @@ pub struct CreateProposal<'info> {
     )]
     pub proposal: Account<'info, Proposal>,
 
+    #[account(seeds = [b"config", fund.key().as_ref()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// Round the proposal applies to
+    #[account(seeds = [b"round", fund.key().as_ref(), round.id.to_be_bytes().as_ref()], bump = round.bump)]
+    pub round: Account<'info, Round>,
+
     #[account(mut)]
     pub user: Signer<'info>,
 
@@ -30,20 +49,91 @@ pub fn create_proposal(
     title: String,
     description: String,
     amount_requested: u64,
+    category: u8,
+    milestones: Vec<MilestoneInput>,
+    vesting: Option<VestingSchedule>,
 ) -> Result<()> {
+    // The "all categories" value is reserved for delegations
+    require!(category != Delegation::ALL_CATEGORIES, ErrorCode::InvalidCategory);
+
+    let round = &ctx.accounts.round;
+    let current_time = Clock::get()?.unix_timestamp;
+    require!(
+        round.is_accepting_applications(current_time),
+        ErrorCode::RoundNotAccepting
+    );
+
+    // Milestones, if any, must split the requested amount exactly
+    require!(
+        milestones.len() <= Milestone::MAX_MILESTONES,
+        ErrorCode::InvalidMilestones
+    );
+    if !milestones.is_empty() {
+        let mut total: u64 = 0;
+        for milestone in &milestones {
+            require!(milestone.amount > 0, ErrorCode::InvalidMilestones);
+            require!(
+                milestone.description.len() <= Milestone::MAX_DESCRIPTION_LEN,
+                ErrorCode::InvalidMilestones
+            );
+            total = total
+                .checked_add(milestone.amount)
+                .ok_or(ErrorCode::InvalidMilestones)?;
+        }
+        require!(total == amount_requested, ErrorCode::InvalidMilestones);
+    }
+
+    // Vesting is an alternative to milestones, not a combination
+    if let Some(schedule) = vesting {
+        require!(milestones.is_empty(), ErrorCode::InvalidPayoutMode);
+        require!(
+            schedule.start <= schedule.cliff
+                && schedule.cliff <= schedule.end
+                && schedule.start < schedule.end,
+            ErrorCode::InvalidVestingSchedule
+        );
+        // Everything must be claimable before the round closes
+        require!(
+            schedule.end <= round.payout_deadline,
+            ErrorCode::InvalidVestingSchedule
+        );
+    }
+
     let proposal = &mut ctx.accounts.proposal;
     proposal.id = ctx.accounts.user_profile.proposal_count;
+    proposal.fund = ctx.accounts.fund.key();
     proposal.owner = ctx.accounts.user.key();
     proposal.title = title;
     proposal.description = description;
     proposal.amount_requested = amount_requested;
+    proposal.category = category;
+    // The round decides how its proposals are voted on, not the applicant
+    proposal.voting_mode = round.voting_mode;
     proposal.status = ProposalStatus::Pending;
-    proposal.vote_count = 0;
+    proposal.yes_votes = 0;
+    proposal.no_votes = 0;
+    proposal.abstain_votes = 0;
     proposal.bump = ctx.bumps.proposal;
-    proposal.created_at = Clock::get()?.unix_timestamp;
+    proposal.created_at = current_time;
     proposal.finalized_at = 0;
 
     proposal.funding_approvals = Vec::new();
+    proposal.params = ctx.accounts.config.params;
+    proposal.milestones = milestones
+        .into_iter()
+        .map(|milestone| Milestone {
+            amount: milestone.amount,
+            description: milestone.description,
+            status: MilestoneStatus::Pending,
+            approvals: Vec::new(),
+        })
+        .collect();
+    proposal.vesting = vesting;
+    proposal.vesting_revoked_at = 0;
+    proposal.amount_claimed = 0;
+    proposal.requested_mint = round.mint;
+    proposal.round_id = round.id;
+    proposal.voting_end = round.voting_end;
 
     ctx.accounts.user_profile.proposal_count += 1;
     Ok(())
@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use crate::state::{Fund, Proposal, ProposalStatus, Round, TokenLedger, Vault};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u64, owner: Pubkey)]
+pub struct ExpireProposal<'info> {
+    #[account(seeds = [b"fund", fund.id.to_be_bytes().as_ref()], bump = fund.bump)]
+    pub fund: Account<'info, Fund>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal", fund.key().as_ref(), owner.as_ref(), proposal_id.to_be_bytes().as_ref()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        mut,
+        seeds = [b"round", fund.key().as_ref(), proposal.round_id.to_be_bytes().as_ref()],
+        bump = round.bump
+    )]
+    pub round: Account<'info, Round>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", fund.key().as_ref(), proposal.round_id.to_be_bytes().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    /// Token ledger of the requested mint - required to release an approved token grant
+    #[account(
+        mut,
+        seeds = [b"token_ledger", vault.key().as_ref(), proposal.requested_mint.unwrap_or_default().as_ref()],
+        bump = ledger.bump
+    )]
+    pub ledger: Option<Account<'info, TokenLedger>>,
+}
+
+/// Permissionless - once the round's payout deadline passes, whatever the
+/// proposal left unclaimed goes back to the round budget and the vault
+pub fn expire_proposal(
+    ctx: Context<ExpireProposal>,
+    _proposal_id: u64,
+    _owner: Pubkey,
+) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+    require!(
+        current_time > ctx.accounts.round.payout_deadline,
+        ErrorCode::PayoutDeadlineNotPassed
+    );
+
+    let proposal = &mut ctx.accounts.proposal;
+    let released = proposal.outstanding_allocation();
+    let uncommitted = proposal.outstanding_commitment();
+    proposal.transition_to(ProposalStatus::Expired)?;
+    ctx.accounts.round.release(released);
+    ctx.accounts.vault.release_grant(
+        ctx.accounts.ledger.as_deref_mut(),
+        proposal.requested_mint,
+        uncommitted,
+    )?;
+
+    msg!("Proposal {} expired, {} released to the vault", proposal.id, uncommitted);
+    Ok(())
+}
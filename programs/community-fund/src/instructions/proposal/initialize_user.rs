@@ -1,11 +1,14 @@
 use anchor_lang::prelude::*;
-use crate::state::UserProfile;
+use crate::state::{Fund, UserProfile};
 
 #[derive(Accounts)]
 pub struct InitializeUser<'info> {
+    #[account(seeds = [b"fund", fund.id.to_be_bytes().as_ref()], bump = fund.bump)]
+    pub fund: Account<'info, Fund>,
+
     #[account(
         init,
-        seeds = [b"user_profile", user.key().as_ref()],
+        seeds = [b"user_profile", fund.key().as_ref(), user.key().as_ref()],
         bump,
         payer = user,
         space = 8 + UserProfile::INIT_SPACE
@@ -0,0 +1,118 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+use crate::instructions::proposal::approve_funding::record_tiered_approval;
+use crate::state::{Config, Fund, MilestoneStatus, Proposal, ProposalStatus, TokenLedger, Vault};
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct SubmitMilestone<'info> {
+    #[account(seeds = [b"fund", fund.id.to_be_bytes().as_ref()], bump = fund.bump)]
+    pub fund: Account<'info, Fund>,
+
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal", fund.key().as_ref(), owner.key().as_ref(), proposal_id.to_be_bytes().as_ref()],
+        bump = proposal.bump,
+        has_one = owner
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
+/// Owner marks a milestone as delivered so admins can review it
+pub fn submit_milestone(
+    ctx: Context<SubmitMilestone>,
+    _proposal_id: u64,
+    index: u8,
+) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    require!(
+        proposal.status == ProposalStatus::Approved,
+        ErrorCode::NotApproved
+    );
+
+    let milestone = proposal
+        .milestones
+        .get_mut(index as usize)
+        .ok_or(ErrorCode::InvalidMilestoneIndex)?;
+    require!(
+        milestone.status == MilestoneStatus::Pending,
+        ErrorCode::InvalidMilestoneStatus
+    );
+    milestone.status = MilestoneStatus::Submitted;
+
+    msg!("Milestone {} submitted for review", index);
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u64, owner: Pubkey)]
+pub struct ApproveMilestone<'info> {
+    #[account(seeds = [b"fund", fund.id.to_be_bytes().as_ref()], bump = fund.bump)]
+    pub fund: Account<'info, Fund>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal", fund.key().as_ref(), owner.as_ref(), proposal_id.to_be_bytes().as_ref()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        seeds = [b"vault", fund.key().as_ref(), proposal.round_id.to_be_bytes().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    /// Token ledger holding the multisig threshold - required when the proposal requests a mint
+    #[account(
+        seeds = [b"token_ledger", vault.key().as_ref(), proposal.requested_mint.unwrap_or_default().as_ref()],
+        bump = ledger.bump
+    )]
+    pub ledger: Option<Account<'info, TokenLedger>>,
+
+    pub admin: Signer<'info>,
+
+    #[account(seeds = [b"config", fund.key().as_ref()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+}
+
+/// Admins sign off a submitted milestone, using the same tiers as approve_funding
+pub fn approve_milestone(
+    ctx: Context<ApproveMilestone>,
+    _proposal_id: u64,
+    _owner: Pubkey,
+    index: u8,
+) -> Result<()> {
+    let admin = ctx.accounts.admin.key();
+    ctx.accounts.config.require_role(&admin, Config::ROLE_TREASURER)?;
+
+    let proposal = &mut ctx.accounts.proposal;
+    require!(
+        proposal.status == ProposalStatus::Approved,
+        ErrorCode::NotApproved
+    );
+
+    let multisig_threshold = proposal.multisig_threshold(ctx.accounts.ledger.as_deref())?;
+    let milestone = proposal
+        .milestones
+        .get_mut(index as usize)
+        .ok_or(ErrorCode::InvalidMilestoneIndex)?;
+    require!(
+        milestone.status == MilestoneStatus::Submitted,
+        ErrorCode::InvalidMilestoneStatus
+    );
+
+    if record_tiered_approval(
+        &mut milestone.approvals,
+        admin,
+        milestone.amount,
+        multisig_threshold,
+        &ctx.accounts.config,
+    )? {
+        milestone.status = MilestoneStatus::Approved;
+        msg!("Milestone {} approved", index);
+    }
+    Ok(())
+}
@@ -1,13 +1,25 @@
 pub mod create;
 pub mod initialize_user;
 pub mod reject;
+pub mod cancel;
 pub mod update;
 pub mod approve_funding;
+pub mod milestone;
+pub mod cancel_vesting;
+pub mod expire;
 pub mod vote;
+pub mod change_vote;
+pub mod withdraw_vote;
 
 pub use create::*;
 pub use initialize_user::*;
 pub use reject::*;
+pub use cancel::*;
 pub use update::*;
 pub use approve_funding::*;
+pub use milestone::*;
+pub use cancel_vesting::*;
+pub use expire::*;
 pub use vote::*;
+pub use change_vote::*;
+pub use withdraw_vote::*;
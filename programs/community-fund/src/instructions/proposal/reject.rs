@@ -1,21 +1,32 @@
 use anchor_lang::prelude::*;
-use crate::state::{Config, Proposal, ProposalStatus};
+use crate::state::{Config, Fund, Proposal, ProposalStatus, Round};
 use crate::errors::ErrorCode;
 
 #[derive(Accounts)]
 #[instruction(proposal_id: u64, owner: Pubkey)]
 pub struct RejectProposal<'info> {
+    #[account(seeds = [b"fund", fund.id.to_be_bytes().as_ref()], bump = fund.bump)]
+    pub fund: Account<'info, Fund>,
+
     #[account(
         mut,
-        seeds = [b"proposal", owner.as_ref(), proposal_id.to_be_bytes().as_ref()],
+        seeds = [b"proposal", fund.key().as_ref(), owner.as_ref(), proposal_id.to_be_bytes().as_ref()],
         bump = proposal.bump
     )]
     pub proposal: Account<'info, Proposal>,
 
+    /// Round whose budget is released if the proposal had passed
+    #[account(
+        mut,
+        seeds = [b"round", fund.key().as_ref(), proposal.round_id.to_be_bytes().as_ref()],
+        bump = round.bump
+    )]
+    pub round: Account<'info, Round>,
+
     #[account(mut)]
     pub admin: Signer<'info>,
 
-    #[account(seeds = [b"config"], bump = config.bump)]
+    #[account(seeds = [b"config", fund.key().as_ref()], bump = config.bump)]
     pub config: Account<'info, Config>,
 }
 
@@ -28,13 +39,12 @@ pub fn reject_proposal(
     
     // Verify admin is authorized
     let admin = ctx.accounts.admin.key();
-    require!(
-        ctx.accounts.config.admins.contains(&admin),
-        ErrorCode::Unauthorized
-    );
+    ctx.accounts.config.require_role(&admin, Config::ROLE_REVIEWER)?;
     
     let proposal = &mut ctx.accounts.proposal;
-    proposal.status = ProposalStatus::Rejected;
+    let released = proposal.outstanding_allocation();
+    proposal.transition_to(ProposalStatus::Rejected)?;
+    ctx.accounts.round.release(released);
     Ok(())
 }
 
@@ -1,14 +1,17 @@
 use anchor_lang::prelude::*;
-use crate::state::Proposal;
+use crate::state::{Fund, Proposal};
 
 #[derive(Accounts)]
 #[instruction(proposal_id: u64)]
 pub struct UpdateProposal<'info> {
+    #[account(seeds = [b"fund", fund.id.to_be_bytes().as_ref()], bump = fund.bump)]
+    pub fund: Account<'info, Fund>,
+
     pub owner: Signer<'info>,
 
     #[account(
         mut,
-        seeds = [b"proposal", owner.key().as_ref(), proposal_id.to_be_bytes().as_ref()],
+        seeds = [b"proposal", fund.key().as_ref(), owner.key().as_ref(), proposal_id.to_be_bytes().as_ref()],
         bump = proposal.bump,
         has_one = owner
     )]
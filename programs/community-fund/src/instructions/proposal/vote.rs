@@ -1,44 +1,290 @@
 use crate::errors::ErrorCode;
-use crate::state::{Proposal, VoteAccount};
+use crate::state::{
+    Delegation, DelegationReceipt, Fund, Proposal, ProposalStatus, VoiceCredits, VoteAccount,
+    VoteChoice, VoterLock, VotingMode,
+};
 use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Allocate, Assign, CreateAccount, Transfer};
 
 #[derive(Accounts)]
 #[instruction(proposal_id: u64, owner: Pubkey)]
 pub struct VoteOnProposal<'info> {
-    #[account(init, seeds=[b"vote", user.key().as_ref(), owner.as_ref(), proposal_id.to_be_bytes().as_ref()], bump, payer = user, space = 8 + VoteAccount::INIT_SPACE)]
-    pub vote_account: Account<'info, VoteAccount>,
-
-    #[account(mut)]
-    pub user: Signer<'info>,
+    #[account(seeds = [b"fund", fund.id.to_be_bytes().as_ref()], bump = fund.bump)]
+    pub fund: Account<'info, Fund>,
 
     #[account(mut, seeds = [
         b"proposal",
+        fund.key().as_ref(),
         owner.as_ref(),
         proposal_id.to_be_bytes().as_ref()
     ], bump = proposal.bump)]
     pub proposal: Account<'info, Proposal>,
 
+    #[account(init, seeds=[b"vote", proposal.key().as_ref(), user.key().as_ref()], bump, payer = user, space = 8 + VoteAccount::INIT_SPACE)]
+    pub vote_account: Account<'info, VoteAccount>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// Voter's escrow lock - vote weight is derived from it.
+    /// Optional so a delegate without tokens of their own can still vote.
+    #[account(seeds = [b"voter_lock", fund.key().as_ref(), user.key().as_ref()], bump = voter_lock.bump)]
+    pub voter_lock: Option<Account<'info, VoterLock>>,
+
+    /// Must not exist - otherwise a delegate already cast this voter's weight
+    /// CHECK: Only checked for emptiness, address is fixed by seeds
+    #[account(
+        seeds = [b"delegation_receipt", proposal.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub delegation_receipt: UncheckedAccount<'info>,
+
+    /// Credit balance charged for quadratic votes
+    #[account(mut, seeds = [b"voice_credits", fund.key().as_ref(), user.key().as_ref()], bump = voice_credits.bump)]
+    pub voice_credits: Option<Account<'info, VoiceCredits>>,
+
     pub system_program: Program<'info, System>,
 }
 
-pub fn vote_on_proposal(
-    ctx: Context<VoteOnProposal>,
+/// Remaining accounts come in groups of four, one group per delegator:
+/// `[delegation, delegator's voter_lock, delegator's vote PDA, delegation_receipt PDA]`
+pub fn vote_on_proposal<'info>(
+    ctx: Context<'_, '_, 'info, 'info, VoteOnProposal<'info>>,
     _proposal_id: u64,
     _owner: Pubkey,
-    token_weight: u64,
+    choice: VoteChoice,
+    quadratic_votes: Option<u64>,
 ) -> Result<()> {
-    let vote = &mut ctx.accounts.vote_account;
     let current_time = Clock::get()?.unix_timestamp;
-    let proposal_created_at = ctx.accounts.proposal.created_at;
+    let voting_ends_at = ctx.accounts.proposal.voting_end;
+    require!(current_time < voting_ends_at, ErrorCode::VotingExpired);
     require!(
-        current_time - proposal_created_at < 604800,
-        ErrorCode::VotingExpired
+        ctx.accounts.proposal.status == ProposalStatus::Pending,
+        ErrorCode::ProposalNotActive
     );
+
+    require!(
+        ctx.accounts.delegation_receipt.data_is_empty(),
+        ErrorCode::DelegationAlreadyUsed
+    );
+
+    if ctx.accounts.proposal.voting_mode == VotingMode::Quadratic {
+        return cast_quadratic_vote(ctx, choice, quadratic_votes, current_time);
+    }
+    require!(quadratic_votes.is_none(), ErrorCode::InvalidVotingMode);
+
+    // Weight comes from the voter's locked tokens, not from the caller
+    let own_weight = match &ctx.accounts.voter_lock {
+        Some(voter_lock) => {
+            // Locked tokens must stay in escrow until voting ends, so they can't vote twice
+            require!(
+                voter_lock.lock_end >= voting_ends_at,
+                ErrorCode::LockExpiresBeforeVotingEnds
+            );
+            voter_lock.voting_power(current_time)
+        }
+        None => 0,
+    };
+
+    let groups = ctx.remaining_accounts.chunks_exact(4);
+    require!(
+        groups.remainder().is_empty(),
+        ErrorCode::InvalidDelegationAccounts
+    );
+    let mut delegated_weight: u64 = 0;
+    let delegator_count = groups.len() as u32;
+    for group in groups {
+        delegated_weight += cast_delegated_weight(
+            &ctx.accounts.user,
+            &ctx.accounts.proposal,
+            &ctx.accounts.system_program,
+            group,
+            current_time,
+        )?;
+    }
+
+    let token_weight = own_weight + delegated_weight;
+    require!(token_weight > 0, ErrorCode::NoVotingPower);
+
+    let vote = &mut ctx.accounts.vote_account;
     vote.bump = ctx.bumps.vote_account;
     vote.timestamp = current_time;
     vote.token_weight = token_weight;
+    vote.delegated_weight = delegated_weight;
+    vote.delegator_count = delegator_count;
+    vote.choice = choice;
 
     let proposal = &mut ctx.accounts.proposal;
-    proposal.vote_count += token_weight;
+    proposal.add_vote(choice, token_weight);
     Ok(())
 }
+
+/// Charges n² voice credits for n effective votes
+fn cast_quadratic_vote(
+    ctx: Context<VoteOnProposal>,
+    choice: VoteChoice,
+    quadratic_votes: Option<u64>,
+    current_time: i64,
+) -> Result<()> {
+    // Credits are personal, so they can't be delegated
+    require!(
+        ctx.remaining_accounts.is_empty(),
+        ErrorCode::InvalidVotingMode
+    );
+    let votes = quadratic_votes.ok_or(ErrorCode::InvalidVotingMode)?;
+    require!(votes > 0, ErrorCode::NoVotingPower);
+
+    let voice_credits = ctx
+        .accounts
+        .voice_credits
+        .as_mut()
+        .ok_or(ErrorCode::InsufficientCredits)?;
+    let cost = votes
+        .checked_mul(votes)
+        .ok_or(ErrorCode::InsufficientCredits)?;
+    require!(
+        voice_credits.balance >= cost,
+        ErrorCode::InsufficientCredits
+    );
+    voice_credits.balance -= cost;
+
+    let vote = &mut ctx.accounts.vote_account;
+    vote.bump = ctx.bumps.vote_account;
+    vote.timestamp = current_time;
+    vote.token_weight = votes;
+    vote.credits_spent = cost;
+    vote.choice = choice;
+
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.add_vote(choice, votes);
+
+    msg!("Quadratic vote: {} votes for {} credits", votes, cost);
+    Ok(())
+}
+
+/// Validates one delegator's accounts, writes their receipt and returns their weight
+fn cast_delegated_weight<'info>(
+    delegate: &Signer<'info>,
+    proposal: &Account<'info, Proposal>,
+    system_program: &Program<'info, System>,
+    group: &'info [AccountInfo<'info>],
+    current_time: i64,
+) -> Result<u64> {
+    let delegation: Account<Delegation> = Account::try_from(&group[0])?;
+    require_keys_eq!(delegation.fund, proposal.fund, ErrorCode::InvalidDelegationAccounts);
+    require_keys_eq!(delegation.delegate, delegate.key(), ErrorCode::InvalidDelegate);
+    require!(
+        delegation.covers(proposal.category),
+        ErrorCode::DelegationCategoryMismatch
+    );
+
+    let delegator = delegation.delegator;
+    let voter_lock: Account<VoterLock> = Account::try_from(&group[1])?;
+    require_keys_eq!(voter_lock.fund, proposal.fund, ErrorCode::InvalidDelegationAccounts);
+    require_keys_eq!(voter_lock.owner, delegator, ErrorCode::InvalidDelegationAccounts);
+    require!(
+        voter_lock.lock_end >= proposal.voting_end,
+        ErrorCode::LockExpiresBeforeVotingEnds
+    );
+
+    // A delegator who voted directly keeps their own vote
+    let proposal_key = proposal.key();
+    let (delegator_vote, _) = Pubkey::find_program_address(
+        &[b"vote", proposal_key.as_ref(), delegator.as_ref()],
+        &crate::ID,
+    );
+    require_keys_eq!(group[2].key(), delegator_vote, ErrorCode::InvalidDelegationAccounts);
+    require!(group[2].data_is_empty(), ErrorCode::DelegatorAlreadyVoted);
+
+    // The receipt is keyed by delegator, so the same weight can't be cast twice
+    let receipt_info = &group[3];
+    let (receipt_key, receipt_bump) = Pubkey::find_program_address(
+        &[b"delegation_receipt", proposal_key.as_ref(), delegator.as_ref()],
+        &crate::ID,
+    );
+    require_keys_eq!(receipt_info.key(), receipt_key, ErrorCode::InvalidDelegationAccounts);
+    require!(receipt_info.data_is_empty(), ErrorCode::DelegationAlreadyUsed);
+
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"delegation_receipt",
+        proposal_key.as_ref(),
+        delegator.as_ref(),
+        &[receipt_bump],
+    ]];
+    create_receipt_account(delegate, receipt_info, system_program, signer_seeds)?;
+
+    let weight = voter_lock.voting_power(current_time);
+    let receipt = DelegationReceipt {
+        delegator,
+        delegate: delegate.key(),
+        weight,
+        bump: receipt_bump,
+    };
+    receipt.try_serialize(&mut &mut receipt_info.try_borrow_mut_data()?[..])?;
+
+    Ok(weight)
+}
+
+/// Creates the receipt PDA the way Anchor's `init` does. The address is predictable,
+/// so anyone may already have sent lamports to it - then `create_account` would fail
+/// and the delegate could never cast this weight. Top up, allocate and assign instead.
+fn create_receipt_account<'info>(
+    payer: &Signer<'info>,
+    receipt_info: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let space = 8 + DelegationReceipt::INIT_SPACE;
+    let rent = Rent::get()?.minimum_balance(space);
+    let current_lamports = receipt_info.lamports();
+
+    if current_lamports == 0 {
+        return system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.to_account_info(),
+                CreateAccount {
+                    from: payer.to_account_info(),
+                    to: receipt_info.clone(),
+                },
+                signer_seeds,
+            ),
+            rent,
+            space as u64,
+            &crate::ID,
+        );
+    }
+
+    let top_up = rent.saturating_sub(current_lamports);
+    if top_up > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                Transfer {
+                    from: payer.to_account_info(),
+                    to: receipt_info.clone(),
+                },
+            ),
+            top_up,
+        )?;
+    }
+    system_program::allocate(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            Allocate {
+                account_to_allocate: receipt_info.clone(),
+            },
+            signer_seeds,
+        ),
+        space as u64,
+    )?;
+    system_program::assign(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            Assign {
+                account_to_assign: receipt_info.clone(),
+            },
+            signer_seeds,
+        ),
+        &crate::ID,
+    )
+}
@@ -0,0 +1,98 @@
+use crate::errors::ErrorCode;
+use crate::state::{DelegationReceipt, Fund, Proposal, ProposalStatus, VoiceCredits, VoteAccount};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u64, owner: Pubkey)]
+pub struct WithdrawVote<'info> {
+    #[account(seeds = [b"fund", fund.id.to_be_bytes().as_ref()], bump = fund.bump)]
+    pub fund: Account<'info, Fund>,
+
+    /// Closed on withdrawal - rent goes back to the voter
+    #[account(
+        mut,
+        seeds = [b"vote", proposal.key().as_ref(), user.key().as_ref()],
+        bump = vote_account.bump,
+        close = user
+    )]
+    pub vote_account: Account<'info, VoteAccount>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal", fund.key().as_ref(), owner.as_ref(), proposal_id.to_be_bytes().as_ref()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// Refunded with the credits spent on a quadratic vote
+    #[account(mut, seeds = [b"voice_credits", fund.key().as_ref(), user.key().as_ref()], bump = voice_credits.bump)]
+    pub voice_credits: Option<Account<'info, VoiceCredits>>,
+}
+
+/// Remaining accounts are the delegation receipts written with this vote, one per delegator.
+/// Once the proposal is cancelled the vote can still be closed for its rent and credits,
+/// at any time, while the frozen tallies stay as they are.
+pub fn withdraw_vote<'info>(
+    ctx: Context<'_, '_, 'info, 'info, WithdrawVote<'info>>,
+    _proposal_id: u64,
+    _owner: Pubkey,
+) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+    let proposal = &mut ctx.accounts.proposal;
+    let vote = &ctx.accounts.vote_account;
+    if proposal.status != ProposalStatus::Cancelled {
+        require!(
+            current_time < proposal.voting_end,
+            ErrorCode::VotingExpired
+        );
+        require!(
+            proposal.status == ProposalStatus::Pending,
+            ErrorCode::ProposalNotActive
+        );
+
+        // Delegated weight is withdrawn too; closing its receipts lets the delegators vote again
+        proposal.remove_vote(vote.choice, vote.token_weight);
+    }
+
+    require!(
+        ctx.remaining_accounts.len() == vote.delegator_count as usize,
+        ErrorCode::InvalidDelegationAccounts
+    );
+    let proposal_key = proposal.key();
+    for receipt_info in ctx.remaining_accounts {
+        close_delegation_receipt(receipt_info, &proposal_key, &ctx.accounts.user)?;
+    }
+
+    if vote.credits_spent > 0 {
+        let voice_credits = ctx
+            .accounts
+            .voice_credits
+            .as_mut()
+            .ok_or(ErrorCode::InsufficientCredits)?;
+        voice_credits.balance += vote.credits_spent;
+    }
+
+    msg!("Vote withdrawn, weight {}", vote.token_weight);
+    Ok(())
+}
+
+/// Closes one receipt cast by `delegate` on this proposal; the rent goes back to the delegate
+fn close_delegation_receipt<'info>(
+    receipt_info: &'info AccountInfo<'info>,
+    proposal_key: &Pubkey,
+    delegate: &Signer<'info>,
+) -> Result<()> {
+    let receipt: Account<DelegationReceipt> = Account::try_from(receipt_info)?;
+    require_keys_eq!(receipt.delegate, delegate.key(), ErrorCode::InvalidDelegationAccounts);
+
+    let (receipt_key, _) = Pubkey::find_program_address(
+        &[b"delegation_receipt", proposal_key.as_ref(), receipt.delegator.as_ref()],
+        &crate::ID,
+    );
+    require_keys_eq!(receipt_info.key(), receipt_key, ErrorCode::InvalidDelegationAccounts);
+
+    receipt.close(delegate.to_account_info())
+}
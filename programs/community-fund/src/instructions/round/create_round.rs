@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+use crate::state::{Config, Fund, Round, Vault, VotingMode};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+#[instruction(round_id: u64)]
+pub struct CreateRound<'info> {
+    #[account(seeds = [b"fund", fund.id.to_be_bytes().as_ref()], bump = fund.bump)]
+    pub fund: Account<'info, Fund>,
+
+    #[account(
+        init,
+        seeds = [b"round", fund.key().as_ref(), round_id.to_be_bytes().as_ref()],
+        bump,
+        payer = admin,
+        space = 8 + Round::INIT_SPACE
+    )]
+    pub round: Account<'info, Round>,
+
+    /// The round's own treasury
+    #[account(
+        init,
+        seeds = [b"vault", fund.key().as_ref(), round_id.to_be_bytes().as_ref()],
+        bump,
+        payer = admin,
+        space = 8 + Vault::INIT_SPACE
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(seeds = [b"config", fund.key().as_ref()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Opens a round. Voting runs until `application_end` plus the configured voting period,
+/// and every proposal in the round is voted on in `voting_mode`.
+#[allow(clippy::too_many_arguments)]
+pub fn create_round(
+    ctx: Context<CreateRound>,
+    round_id: u64,
+    mint: Option<Pubkey>,
+    voting_mode: VotingMode,
+    budget: u64,
+    application_start: i64,
+    application_end: i64,
+    payout_deadline: i64,
+) -> Result<()> {
+    let admin = ctx.accounts.admin.key();
+    ctx.accounts.config.require_role(&admin, Config::ROLE_TREASURER)?;
+
+    let voting_end = application_end + ctx.accounts.config.params.voting_period;
+    require!(budget > 0, ErrorCode::InvalidRoundSchedule);
+    require!(
+        application_start < application_end && voting_end <= payout_deadline,
+        ErrorCode::InvalidRoundSchedule
+    );
+
+    let round = &mut ctx.accounts.round;
+    round.fund = ctx.accounts.fund.key();
+    round.id = round_id;
+    round.mint = mint;
+    round.voting_mode = voting_mode;
+    round.budget = budget;
+    round.allocated = 0;
+    round.application_start = application_start;
+    round.application_end = application_end;
+    round.voting_end = voting_end;
+    round.payout_deadline = payout_deadline;
+    round.bump = ctx.bumps.round;
+
+    let vault = &mut ctx.accounts.vault;
+    vault.total_deposited = 0;
+    vault.total_claimed = 0;
+    vault.bump = ctx.bumps.vault;
+
+    msg!("Round {} created with budget {}", round_id, budget);
+    Ok(())
+}
@@ -0,0 +1,3 @@
+pub mod create_round;
+
+pub use create_round::*;
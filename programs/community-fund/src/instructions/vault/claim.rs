@@ -1,23 +1,41 @@
 use anchor_lang::prelude::*;
-use crate::state::{Proposal, ProposalStatus, Vault};
+use crate::state::{Config, Fund, Proposal, ProposalStatus, Round, Vault};
 use crate::errors::ErrorCode;
 
 #[derive(Accounts)]
 #[instruction(proposal_id: u64)]
 pub struct ClaimFunds<'info> {
+    #[account(seeds = [b"fund", fund.id.to_be_bytes().as_ref()], bump = fund.bump)]
+    pub fund: Account<'info, Fund>,
+
+    /// Payouts stop while the program is paused
+    #[account(
+        seeds = [b"config", fund.key().as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Account<'info, Config>,
+
     #[account(
         mut,
-        seeds = [b"proposal", owner.key().as_ref(), proposal_id.to_be_bytes().as_ref()],
+        seeds = [b"proposal", fund.key().as_ref(), owner.key().as_ref(), proposal_id.to_be_bytes().as_ref()],
         bump = proposal.bump,
         has_one = owner
     )]
     pub proposal: Account<'info, Proposal>,
 
+    #[account(seeds = [b"round", fund.key().as_ref(), proposal.round_id.to_be_bytes().as_ref()], bump = round.bump)]
+    pub round: Account<'info, Round>,
+
     /// Proposal owner who will receive funds
     #[account(mut)]
     pub owner: Signer<'info>,
 
-    #[account(mut, seeds = [b"vault"], bump = vault.bump)]
+    #[account(
+        mut,
+        seeds = [b"vault", fund.key().as_ref(), proposal.round_id.to_be_bytes().as_ref()],
+        bump = vault.bump
+    )]
     pub vault: Account<'info, Vault>,
 
     pub system_program: Program<'info, System>,
@@ -28,31 +46,45 @@ pub fn claim_funds(
     _proposal_id: u64,
 ) -> Result<()> {
     let proposal = &mut ctx.accounts.proposal;
+    let current_time = Clock::get()?.unix_timestamp;
 
-    // Check 1: Proposal must be finalized (passed voting)
+    // Check 1: Proposal must be approved by the admins after passing the vote
     require!(
-        proposal.status == ProposalStatus::Finalized,
+        proposal.status == ProposalStatus::Approved,
         ErrorCode::NotApproved
     );
+    require!(
+        current_time <= ctx.accounts.round.payout_deadline,
+        ErrorCode::PayoutDeadlinePassed
+    );
+
+    // Vesting grants are paid out through claim_vested, token grants through claim_token
+    require!(proposal.vesting.is_none(), ErrorCode::InvalidPayoutMode);
+    require!(proposal.requested_mint.is_none(), ErrorCode::InvalidRequestedMint);
+
+    // Check 2: Work out the payout - the full amount, or only approved milestones
+    let payout = proposal.claimable_amount(current_time);
+    require!(payout > 0, ErrorCode::NothingToClaim);
 
-    // Check 2: Vault has enough balance
+    // Check 3: Vault has enough balance above its rent-exempt reserve
     let vault = &ctx.accounts.vault;
-    let vault_balance = vault.to_account_info().lamports();
+    let vault_balance = Vault::spendable_lamports(&vault.to_account_info())?;
 
     require!(
-        vault_balance >= proposal.amount_requested,
+        vault_balance >= payout,
         ErrorCode::InsufficientVaultBalance
     );
 
     // Transfer SOL from vault to owner
-    **vault.to_account_info().try_borrow_mut_lamports()? -= proposal.amount_requested;
-    **ctx.accounts.owner.to_account_info().try_borrow_mut_lamports()? += proposal.amount_requested;
+    **vault.to_account_info().try_borrow_mut_lamports()? -= payout;
+    **ctx.accounts.owner.to_account_info().try_borrow_mut_lamports()? += payout;
 
-    // Update state
-    proposal.status = ProposalStatus::Claimed;
+    // Update state - the proposal is only Claimed once every milestone is paid
+    proposal.record_claim(payout)?;
     let vault_mut = &mut ctx.accounts.vault;
-    vault_mut.total_claimed += proposal.amount_requested;
+    vault_mut.total_claimed += payout;
+    vault_mut.release(payout);
 
-    msg!("Claimed {} lamports from vault", proposal.amount_requested);
+    msg!("Claimed {} lamports from vault", payout);
     Ok(())
 }
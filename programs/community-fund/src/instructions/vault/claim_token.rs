@@ -0,0 +1,129 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{Config, Fund, Proposal, ProposalStatus, Round, TokenLedger, Vault};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct ClaimToken<'info> {
+    #[account(seeds = [b"fund", fund.id.to_be_bytes().as_ref()], bump = fund.bump)]
+    pub fund: Account<'info, Fund>,
+
+    /// Payouts stop while the program is paused
+    #[account(
+        seeds = [b"config", fund.key().as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal", fund.key().as_ref(), owner.key().as_ref(), proposal_id.to_be_bytes().as_ref()],
+        bump = proposal.bump,
+        has_one = owner
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(seeds = [b"round", fund.key().as_ref(), proposal.round_id.to_be_bytes().as_ref()], bump = round.bump)]
+    pub round: Account<'info, Round>,
+
+    /// Proposal owner who will receive funds
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [b"vault", fund.key().as_ref(), proposal.round_id.to_be_bytes().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [b"token_ledger", vault.key().as_ref(), mint.key().as_ref()],
+        bump = ledger.bump
+    )]
+    pub ledger: Account<'info, TokenLedger>,
+
+    #[account(
+        mut,
+        seeds = [b"vault_token", vault.key().as_ref(), mint.key().as_ref()],
+        bump = ledger.token_account_bump
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = owner_token_account.mint == mint.key() @ ErrorCode::InvalidRequestedMint,
+        constraint = owner_token_account.owner == owner.key() @ ErrorCode::InvalidTokenOwner
+    )]
+    pub owner_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Legacy Token program or Token-2022
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn claim_token(
+    ctx: Context<ClaimToken>,
+    _proposal_id: u64,
+) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    // Check 1: Proposal must be approved and request this mint
+    require!(
+        proposal.status == ProposalStatus::Approved,
+        ErrorCode::NotApproved
+    );
+    require!(
+        current_time <= ctx.accounts.round.payout_deadline,
+        ErrorCode::PayoutDeadlinePassed
+    );
+    require!(
+        proposal.requested_mint == Some(ctx.accounts.mint.key()),
+        ErrorCode::InvalidRequestedMint
+    );
+
+    // Check 2: Work out the payout - lump sum, approved milestones or vested amount
+    let payout = proposal.claimable_amount(current_time);
+    require!(payout > 0, ErrorCode::NothingToClaim);
+
+    // Check 3: Vault holds enough of this mint
+    require!(
+        ctx.accounts.vault_token_account.amount >= payout,
+        ErrorCode::InsufficientVaultBalance
+    );
+
+    // Transfer tokens from the vault, signed by the vault PDA
+    let fund_key = ctx.accounts.fund.key();
+    let round_id = proposal.round_id.to_be_bytes();
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"vault",
+        fund_key.as_ref(),
+        round_id.as_ref(),
+        &[ctx.accounts.vault.bump],
+    ]];
+    let cpi_context = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.vault_token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            to: ctx.accounts.owner_token_account.to_account_info(),
+            authority: ctx.accounts.vault.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token_interface::transfer_checked(cpi_context, payout, ctx.accounts.mint.decimals)?;
+
+    // Update state - any transfer fee is borne by the recipient, the vault still paid out `payout`
+    proposal.record_claim(payout)?;
+    let ledger = &mut ctx.accounts.ledger;
+    ledger.total_claimed += payout;
+    ledger.release(payout);
+
+    msg!("Claimed {} tokens of {} from vault", payout, ctx.accounts.mint.key());
+    Ok(())
+}
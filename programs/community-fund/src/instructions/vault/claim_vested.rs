@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+use crate::state::{Config, Fund, Proposal, ProposalStatus, Round, Vault};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct ClaimVested<'info> {
+    #[account(seeds = [b"fund", fund.id.to_be_bytes().as_ref()], bump = fund.bump)]
+    pub fund: Account<'info, Fund>,
+
+    /// Payouts stop while the program is paused
+    #[account(
+        seeds = [b"config", fund.key().as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal", fund.key().as_ref(), owner.key().as_ref(), proposal_id.to_be_bytes().as_ref()],
+        bump = proposal.bump,
+        has_one = owner
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(seeds = [b"round", fund.key().as_ref(), proposal.round_id.to_be_bytes().as_ref()], bump = round.bump)]
+    pub round: Account<'info, Round>,
+
+    /// Proposal owner who will receive funds
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", fund.key().as_ref(), proposal.round_id.to_be_bytes().as_ref()],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn claim_vested(
+    ctx: Context<ClaimVested>,
+    _proposal_id: u64,
+) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    // Check 1: Proposal must be approved and on a vesting schedule
+    require!(
+        proposal.status == ProposalStatus::Approved,
+        ErrorCode::NotApproved
+    );
+    require!(
+        current_time <= ctx.accounts.round.payout_deadline,
+        ErrorCode::PayoutDeadlinePassed
+    );
+    require!(proposal.vesting.is_some(), ErrorCode::InvalidPayoutMode);
+    require!(proposal.requested_mint.is_none(), ErrorCode::InvalidRequestedMint);
+
+    // Check 2: Only what has unlocked since the last claim
+    let vested = proposal.vested_amount(current_time);
+    let payout = proposal.claimable_amount(current_time);
+    require!(payout > 0, ErrorCode::NothingToClaim);
+
+    // Check 3: Vault has enough balance above its rent-exempt reserve
+    let vault = &ctx.accounts.vault;
+    require!(
+        Vault::spendable_lamports(&vault.to_account_info())? >= payout,
+        ErrorCode::InsufficientVaultBalance
+    );
+
+    // Transfer SOL from vault to owner
+    **vault.to_account_info().try_borrow_mut_lamports()? -= payout;
+    **ctx.accounts.owner.to_account_info().try_borrow_mut_lamports()? += payout;
+
+    // Update state - done once nothing more can vest
+    proposal.record_claim(payout)?;
+    let final_amount = proposal.vested_amount(i64::MAX);
+    let vault_mut = &mut ctx.accounts.vault;
+    vault_mut.total_claimed += payout;
+    vault_mut.release(payout);
+
+    msg!("Claimed {} vested lamports ({} of {})", payout, vested, final_amount);
+    Ok(())
+}
@@ -1,12 +1,27 @@
 use anchor_lang::prelude::*;
 use anchor_lang::system_program;
-use crate::state::Vault;
+use crate::state::{DepositorRecord, Fund, Vault};
+use crate::errors::ErrorCode;
 
 #[derive(Accounts)]
+#[instruction(round_id: u64)]
 pub struct DepositToVault<'info> {
-    #[account(mut, seeds = [b"vault"], bump = vault.bump)]
+    #[account(seeds = [b"fund", fund.id.to_be_bytes().as_ref()], bump = fund.bump)]
+    pub fund: Account<'info, Fund>,
+
+    #[account(mut, seeds = [b"vault", fund.key().as_ref(), round_id.to_be_bytes().as_ref()], bump = vault.bump)]
     pub vault: Account<'info, Vault>,
 
+    /// Running record of what this depositor has given to the vault
+    #[account(
+        init_if_needed,
+        seeds = [b"depositor", vault.key().as_ref(), depositor.key().as_ref()],
+        bump,
+        payer = depositor,
+        space = 8 + DepositorRecord::INIT_SPACE
+    )]
+    pub depositor_record: Account<'info, DepositorRecord>,
+
     #[account(mut)]
     pub depositor: Signer<'info>,
 
@@ -15,8 +30,24 @@ pub struct DepositToVault<'info> {
 
 pub fn deposit_to_vault(
     ctx: Context<DepositToVault>,
+    _round_id: u64,
     amount: u64,
+    memo: Option<String>,
 ) -> Result<()> {
+    let memo = memo.unwrap_or_default();
+    require!(
+        memo.len() <= DepositorRecord::MAX_MEMO_LEN,
+        ErrorCode::MemoTooLong
+    );
+
+    // Price the deposit before it lands in the vault
+    let vault_info = ctx.accounts.vault.to_account_info();
+    let available = ctx
+        .accounts
+        .vault
+        .free_balance(Vault::spendable_lamports(&vault_info)?);
+    let shares = ctx.accounts.vault.shares_for_deposit(amount, available);
+
     // Transfer SOL from depositor to vault PDA
     let cpi_context = CpiContext::new(
         ctx.accounts.system_program.to_account_info(),
@@ -31,7 +62,28 @@ pub fn deposit_to_vault(
     // Update vault tracking
     let vault = &mut ctx.accounts.vault;
     vault.total_deposited += amount;
+    vault.total_shares += shares;
+
+    let current_time = Clock::get()?.unix_timestamp;
+    let record = &mut ctx.accounts.depositor_record;
+    if record.deposit_count == 0 {
+        record.vault = vault.key();
+        record.depositor = ctx.accounts.depositor.key();
+        record.first_deposit_at = current_time;
+        record.bump = ctx.bumps.depositor_record;
+    }
+    record.total_contributed += amount;
+    record.shares += shares;
+    record.deposit_count += 1;
+    record.last_deposit_at = current_time;
+    record.last_memo = memo;
 
-    msg!("Deposited {} lamports to vault", amount);
+    msg!(
+        "Deposited {} lamports to vault for {} shares ({} total from {})",
+        amount,
+        shares,
+        record.total_contributed,
+        record.depositor
+    );
     Ok(())
 }
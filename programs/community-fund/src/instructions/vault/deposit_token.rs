@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::{Fund, TokenLedger, Vault};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+#[instruction(round_id: u64)]
+pub struct DepositToken<'info> {
+    #[account(seeds = [b"fund", fund.id.to_be_bytes().as_ref()], bump = fund.bump)]
+    pub fund: Account<'info, Fund>,
+
+    #[account(seeds = [b"vault", fund.key().as_ref(), round_id.to_be_bytes().as_ref()], bump = vault.bump)]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init_if_needed,
+        seeds = [b"token_ledger", vault.key().as_ref(), mint.key().as_ref()],
+        bump,
+        payer = depositor,
+        space = 8 + TokenLedger::INIT_SPACE
+    )]
+    pub ledger: Account<'info, TokenLedger>,
+
+    /// Vault's token account for this mint - only the vault PDA can move these tokens
+    #[account(
+        init_if_needed,
+        seeds = [b"vault_token", vault.key().as_ref(), mint.key().as_ref()],
+        bump,
+        payer = depositor,
+        token::mint = mint,
+        token::authority = vault,
+        token::token_program = token_program
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = depositor_token_account.mint == mint.key() @ ErrorCode::InvalidRequestedMint,
+        constraint = depositor_token_account.owner == depositor.key() @ ErrorCode::InvalidTokenOwner
+    )]
+    pub depositor_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+
+    /// Legacy Token program or Token-2022
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn deposit_token(ctx: Context<DepositToken>, _round_id: u64, amount: u64) -> Result<()> {
+    // Transfer tokens from depositor to the vault's token account
+    let balance_before = ctx.accounts.vault_token_account.amount;
+    let cpi_context = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.depositor_token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            to: ctx.accounts.vault_token_account.to_account_info(),
+            authority: ctx.accounts.depositor.to_account_info(),
+        },
+    );
+    token_interface::transfer_checked(cpi_context, amount, ctx.accounts.mint.decimals)?;
+
+    // Transfer-fee mints deliver less than `amount` - record what actually arrived
+    ctx.accounts.vault_token_account.reload()?;
+    let received = ctx.accounts.vault_token_account.amount - balance_before;
+
+    // Update per-mint tracking
+    let ledger = &mut ctx.accounts.ledger;
+    if ledger.mint == Pubkey::default() {
+        ledger.mint = ctx.accounts.mint.key();
+        ledger.bump = ctx.bumps.ledger;
+        ledger.token_account_bump = ctx.bumps.vault_token_account;
+    }
+    ledger.total_deposited += received;
+
+    msg!("Deposited {} tokens of {} to vault ({} sent)", received, ledger.mint, amount);
+    Ok(())
+}
@@ -1,17 +1,28 @@
 use anchor_lang::prelude::*;
-use crate::state::{Proposal, ProposalStatus};
+use crate::state::{Fund, Proposal, ProposalStatus, Round};
 use crate::errors::ErrorCode;
 
 #[derive(Accounts)]
 #[instruction(proposal_id: u64, owner: Pubkey)]
 pub struct FinalizeProposal<'info> {
+    #[account(seeds = [b"fund", fund.id.to_be_bytes().as_ref()], bump = fund.bump)]
+    pub fund: Account<'info, Fund>,
+
     #[account(
         mut,
-        seeds = [b"proposal", owner.as_ref(), proposal_id.to_be_bytes().as_ref()],
+        seeds = [b"proposal", fund.key().as_ref(), owner.as_ref(), proposal_id.to_be_bytes().as_ref()],
         bump = proposal.bump
     )]
     pub proposal: Account<'info, Proposal>,
 
+    /// Round the proposal belongs to
+    #[account(
+        mut,
+        seeds = [b"round", fund.key().as_ref(), proposal.round_id.to_be_bytes().as_ref()],
+        bump = round.bump
+    )]
+    pub round: Account<'info, Round>,
+
     /// Anyone can call this function
     #[account(mut)]
     pub caller: Signer<'info>,
@@ -25,10 +36,9 @@ pub fn finalize_proposal(
     let proposal = &mut ctx.accounts.proposal;
     let current_time = Clock::get()?.unix_timestamp;
 
-    // Check 1: Voting deadline must be over (7 days = 604800 seconds)
-    let voting_deadline = proposal.created_at + 604800;
+    // Check 1: Voting deadline must be over
     require!(
-        current_time >= voting_deadline,
+        current_time >= proposal.voting_end,
         ErrorCode::VotingStillActive
     );
 
@@ -38,17 +48,29 @@ pub fn finalize_proposal(
         ErrorCode::AlreadyFinalized
     );
 
-    // Check 3: Minimum vote threshold (let's say 100 votes minimum)
-    const MIN_VOTES: u64 = 100;
+    // Check 3: Quorum - total turnout (yes + no + abstain) must reach the mode's quorum
+    // Check 4: Approval - yes must exceed the threshold share of yes + no (abstentions don't count)
+    let params = proposal.params;
+    let quorum = params.quorum_for(proposal.voting_mode);
+    let turnout = proposal.total_votes();
+    let decided = proposal.yes_votes as u128 + proposal.no_votes as u128;
+    let approved = proposal.yes_votes as u128 * 10_000
+        > decided * params.approval_threshold_bps as u128;
 
-    if proposal.vote_count >= MIN_VOTES {
-        proposal.status = ProposalStatus::Finalized;
-        proposal.finalized_at = current_time;
-        msg!("Proposal finalized with {} votes", proposal.vote_count);
+    proposal.finalized_at = current_time;
+    if turnout < quorum {
+        proposal.transition_to(ProposalStatus::Rejected)?;
+        msg!("Proposal rejected - quorum not reached ({}/{})", turnout, quorum);
+    } else if !approved {
+        proposal.transition_to(ProposalStatus::Rejected)?;
+        msg!("Proposal rejected - {} yes / {} no", proposal.yes_votes, proposal.no_votes);
+    } else if !ctx.accounts.round.allocate(proposal.amount_requested) {
+        // Passed the vote, but the round has no budget left for it
+        proposal.transition_to(ProposalStatus::Rejected)?;
+        msg!("Proposal rejected - round budget exhausted");
     } else {
-        proposal.status = ProposalStatus::Rejected;
-        proposal.finalized_at = current_time;
-        msg!("Proposal rejected - insufficient votes ({}/{})", proposal.vote_count, MIN_VOTES);
+        proposal.transition_to(ProposalStatus::Finalized)?;
+        msg!("Proposal finalized with {} yes / {} no", proposal.yes_votes, proposal.no_votes);
     }
 
     Ok(())
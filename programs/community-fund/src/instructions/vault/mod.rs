@@ -1,9 +1,19 @@
-pub mod initialize;
 pub mod deposit;
 pub mod finalize;
 pub mod claim;
+pub mod claim_vested;
+pub mod deposit_token;
+pub mod claim_token;
+pub mod ragequit;
+pub mod spendable;
+pub mod reconcile;
 
-pub use initialize::*;
 pub use deposit::*;
 pub use finalize::*;
 pub use claim::*;
+pub use claim_vested::*;
+pub use deposit_token::*;
+pub use claim_token::*;
+pub use ragequit::*;
+pub use spendable::*;
+pub use reconcile::*;
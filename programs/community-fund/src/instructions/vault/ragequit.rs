@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+use crate::state::{Config, DepositorRecord, Fund, Vault};
+use crate::errors::ErrorCode;
+
+#[derive(Accounts)]
+#[instruction(round_id: u64)]
+pub struct Ragequit<'info> {
+    #[account(seeds = [b"fund", fund.id.to_be_bytes().as_ref()], bump = fund.bump)]
+    pub fund: Account<'info, Fund>,
+
+    /// Payouts stop while the program is paused
+    #[account(
+        seeds = [b"config", fund.key().as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ ErrorCode::ProgramPaused
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(mut, seeds = [b"vault", fund.key().as_ref(), round_id.to_be_bytes().as_ref()], bump = vault.bump)]
+    pub vault: Account<'info, Vault>,
+
+    /// Burned on exit - the rent goes back to the depositor
+    #[account(
+        mut,
+        seeds = [b"depositor", vault.key().as_ref(), depositor.key().as_ref()],
+        bump = depositor_record.bump,
+        has_one = depositor,
+        close = depositor
+    )]
+    pub depositor_record: Account<'info, DepositorRecord>,
+
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+}
+
+/// Redeems the depositor's shares for their part of the vault's uncommitted balance
+pub fn ragequit(ctx: Context<Ragequit>, _round_id: u64) -> Result<()> {
+    let contributed = ctx.accounts.depositor_record.total_contributed;
+    let shares = ctx.accounts.depositor_record.shares;
+    require!(shares > 0, ErrorCode::NoContribution);
+
+    let vault = &ctx.accounts.vault;
+    let vault_info = vault.to_account_info();
+
+    // SOL reserved for approved grants stays behind
+    let available = vault.free_balance(Vault::spendable_lamports(&vault_info)?);
+
+    let payout = vault.payout_for_shares(shares, available);
+
+    **vault_info.try_borrow_mut_lamports()? -= payout;
+    **ctx.accounts.depositor.to_account_info().try_borrow_mut_lamports()? += payout;
+
+    let vault = &mut ctx.accounts.vault;
+    vault.total_burned += contributed;
+    vault.total_shares -= shares;
+    vault.total_withdrawn += payout;
+
+    msg!(
+        "Ragequit: {} lamports returned for {} shares ({} lamports contributed)",
+        payout,
+        shares,
+        contributed
+    );
+    Ok(())
+}
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+use crate::events::VaultReconciled;
+use crate::state::{Fund, Vault};
+
+#[derive(Accounts)]
+#[instruction(round_id: u64)]
+pub struct ReconcileVault<'info> {
+    #[account(seeds = [b"fund", fund.id.to_be_bytes().as_ref()], bump = fund.bump)]
+    pub fund: Account<'info, Fund>,
+
+    #[account(mut, seeds = [b"vault", fund.key().as_ref(), round_id.to_be_bytes().as_ref()], bump = vault.bump)]
+    pub vault: Account<'info, Vault>,
+}
+
+/// Permissionless - books any lamports sent straight to the vault as external donations
+pub fn reconcile_vault(ctx: Context<ReconcileVault>, _round_id: u64) -> Result<()> {
+    let vault_info = ctx.accounts.vault.to_account_info();
+    let lamports = vault_info.lamports();
+    let reserve = Rent::get()?.minimum_balance(vault_info.data_len());
+
+    let vault = &mut ctx.accounts.vault;
+    let expected = vault.expected_lamports(reserve);
+    let new_donations = lamports.saturating_sub(expected);
+    let shortfall = expected.saturating_sub(lamports);
+    vault.external_donations += new_donations;
+
+    if shortfall > 0 {
+        msg!("Vault is {} lamports short of its recorded totals", shortfall);
+    }
+
+    emit!(VaultReconciled {
+        vault: vault.key(),
+        lamports,
+        expected,
+        new_donations,
+        shortfall,
+        external_donations: vault.external_donations,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
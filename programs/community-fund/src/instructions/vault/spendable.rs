@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+use crate::state::{Fund, Vault};
+
+#[derive(Accounts)]
+#[instruction(round_id: u64)]
+pub struct GetSpendableBalance<'info> {
+    #[account(seeds = [b"fund", fund.id.to_be_bytes().as_ref()], bump = fund.bump)]
+    pub fund: Account<'info, Fund>,
+
+    #[account(seeds = [b"vault", fund.key().as_ref(), round_id.to_be_bytes().as_ref()], bump = vault.bump)]
+    pub vault: Account<'info, Vault>,
+}
+
+/// Read-only - lamports the vault can pay out without touching its rent-exempt reserve
+pub fn get_spendable_balance(ctx: Context<GetSpendableBalance>, _round_id: u64) -> Result<u64> {
+    let spendable = Vault::spendable_lamports(&ctx.accounts.vault.to_account_info())?;
+    msg!(
+        "Spendable: {} lamports ({} committed)",
+        spendable,
+        ctx.accounts.vault.committed
+    );
+    Ok(spendable)
+}
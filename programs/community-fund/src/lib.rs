@@ -3,91 +3,321 @@ use anchor_lang::prelude::*;
 declare_id!("6gE2epaU3z6ySCsnwY9fvWyCCTnUMZ97c4jkzvPg52St");
 
 pub mod errors;
+pub mod events;
 pub mod instructions;
 pub mod state;
 
 use instructions::*;
+use state::{
+    AdminChange, EmergencyPolicy, GovernanceParams, MilestoneInput, VestingSchedule, VoteChoice,
+    VotingMode,
+};
 
-#[program]
-pub mod community_fund {
+// Anchor emits its IDL handlers next to the program module and they still call
+// the deprecated `AccountInfo::realloc`, so the allow wraps both
+#[allow(deprecated)]
+mod anchor_program {
     use super::*;
 
-    pub fn initialize_admin(
-        ctx: Context<InitializeAdmin>,
-        admin2: Pubkey,
-        admin3: Pubkey,
-    ) -> Result<()> {
-        instructions::admin::initialize_admin(ctx, admin2, admin3)
-    }
+    #[program]
+    pub mod community_fund {
+        use super::*;
 
-    pub fn transfer_admin(ctx: Context<TransferAdmin>, old_admin: Pubkey, new_admin: Pubkey) -> Result<()> {
-        instructions::admin::transfer_admin(ctx, old_admin, new_admin)
-    }
+        pub fn initialize_admin(
+            ctx: Context<InitializeAdmin>,
+            fund_id: u64,
+            admins: Vec<Pubkey>,
+            roles: Vec<u8>,
+            admin_threshold: u8,
+        ) -> Result<()> {
+            instructions::admin::initialize_admin(ctx, fund_id, admins, roles, admin_threshold)
+        }
 
-    pub fn initialize_user(ctx: Context<InitializeUser>) -> Result<()> {
-        instructions::proposal::initialize_user(ctx)
-    }
+        pub fn grant_voice_credits(ctx: Context<GrantVoiceCredits>, voter: Pubkey, amount: u64) -> Result<()> {
+            instructions::admin::grant_voice_credits(ctx, voter, amount)
+        }
 
-    pub fn create_proposal(
-        ctx: Context<CreateProposal>,
-        title: String,
-        description: String,
-        amount_requested: u64,
-    ) -> Result<()> {
-        instructions::proposal::create_proposal(ctx, title, description, amount_requested)
-    }
+        pub fn update_params(ctx: Context<UpdateParams>, params: GovernanceParams) -> Result<()> {
+            instructions::admin::update_params(ctx, params)
+        }
 
-    pub fn update_proposal(
-        ctx: Context<UpdateProposal>,
-        proposal_id: u64,
-        new_title: String,
-        new_description: String,
-    ) -> Result<()> {
-        instructions::proposal::update_proposal(ctx, proposal_id, new_title, new_description)
-    }
+        pub fn set_token_multisig_threshold(
+            ctx: Context<SetTokenMultisigThreshold>,
+            round_id: u64,
+            mint: Pubkey,
+            threshold: u64,
+        ) -> Result<()> {
+            instructions::admin::set_token_multisig_threshold(ctx, round_id, mint, threshold)
+        }
 
-    pub fn reject_proposal(
-        ctx: Context<RejectProposal>,
-        proposal_id: u64,
-        owner: Pubkey,
-    ) -> Result<()> {
-        instructions::proposal::reject_proposal(ctx, proposal_id, owner)
-    }
+        pub fn propose_admin_change(ctx: Context<ProposeAdminChange>, change: AdminChange) -> Result<()> {
+            instructions::admin::propose_admin_change(ctx, change)
+        }
 
-    pub fn approve_funding(
-        ctx: Context<ApproveFunding>,
-        proposal_id: u64,
-        owner: Pubkey,
-    ) -> Result<()> {
-        instructions::proposal::approve_funding(ctx, proposal_id, owner)
-    }
+        pub fn approve_admin_change(ctx: Context<ApproveAdminChange>, change_id: u64) -> Result<()> {
+            instructions::admin::approve_admin_change(ctx, change_id)
+        }
 
-    pub fn vote_on_proposal(
-        ctx: Context<VoteOnProposal>,
-        proposal_id: u64,
-        owner: Pubkey,
-        token_weight: u64
-    ) -> Result<()> {
-        instructions::proposal::vote_on_proposal(ctx, proposal_id, owner, token_weight)
-    }
+        pub fn execute_admin_change(ctx: Context<ExecuteAdminChange>, change_id: u64) -> Result<()> {
+            instructions::admin::execute_admin_change(ctx, change_id)
+        }
 
-    pub fn initialize_vault(ctx: Context<InitializeVault>) -> Result<()> {
-        instructions::vault::initialize_vault(ctx)
-    }
+        pub fn cancel_admin_change(ctx: Context<CancelAdminChange>, change_id: u64) -> Result<()> {
+            instructions::admin::cancel_admin_change(ctx, change_id)
+        }
 
-    pub fn deposit_to_vault(ctx: Context<DepositToVault>, amount: u64) -> Result<()> {
-        instructions::vault::deposit_to_vault(ctx, amount)
-    }
+        pub fn nominate_admin(ctx: Context<NominateAdmin>, nominee: Pubkey) -> Result<()> {
+            instructions::admin::nominate_admin(ctx, nominee)
+        }
 
-    pub fn finalize_proposal(
-        ctx: Context<FinalizeProposal>,
-        proposal_id: u64,
-        owner: Pubkey,
-    ) -> Result<()> {
-        instructions::vault::finalize_proposal(ctx, proposal_id, owner)
-    }
+        pub fn accept_admin(ctx: Context<AcceptAdmin>, admin: Pubkey) -> Result<()> {
+            instructions::admin::accept_admin(ctx, admin)
+        }
+
+        pub fn cancel_admin_nomination(ctx: Context<CancelAdminNomination>) -> Result<()> {
+            instructions::admin::cancel_admin_nomination(ctx)
+        }
+
+        pub fn initialize_user(ctx: Context<InitializeUser>) -> Result<()> {
+            instructions::proposal::initialize_user(ctx)
+        }
+
+        pub fn create_proposal(
+            ctx: Context<CreateProposal>,
+            title: String,
+            description: String,
+            amount_requested: u64,
+            category: u8,
+            milestones: Vec<MilestoneInput>,
+            vesting: Option<VestingSchedule>,
+        ) -> Result<()> {
+            instructions::proposal::create_proposal(
+                ctx,
+                title,
+                description,
+                amount_requested,
+                category,
+                milestones,
+                vesting,
+            )
+        }
+
+        pub fn update_proposal(
+            ctx: Context<UpdateProposal>,
+            proposal_id: u64,
+            new_title: String,
+            new_description: String,
+        ) -> Result<()> {
+            instructions::proposal::update_proposal(ctx, proposal_id, new_title, new_description)
+        }
+
+        pub fn reject_proposal(
+            ctx: Context<RejectProposal>,
+            proposal_id: u64,
+            owner: Pubkey,
+        ) -> Result<()> {
+            instructions::proposal::reject_proposal(ctx, proposal_id, owner)
+        }
+
+        pub fn cancel_proposal(ctx: Context<CancelProposal>, proposal_id: u64) -> Result<()> {
+            instructions::proposal::cancel_proposal(ctx, proposal_id)
+        }
+
+        pub fn approve_funding(
+            ctx: Context<ApproveFunding>,
+            proposal_id: u64,
+            owner: Pubkey,
+        ) -> Result<()> {
+            instructions::proposal::approve_funding(ctx, proposal_id, owner)
+        }
+
+        pub fn submit_milestone(
+            ctx: Context<SubmitMilestone>,
+            proposal_id: u64,
+            index: u8,
+        ) -> Result<()> {
+            instructions::proposal::submit_milestone(ctx, proposal_id, index)
+        }
+
+        pub fn approve_milestone(
+            ctx: Context<ApproveMilestone>,
+            proposal_id: u64,
+            owner: Pubkey,
+            index: u8,
+        ) -> Result<()> {
+            instructions::proposal::approve_milestone(ctx, proposal_id, owner, index)
+        }
+
+        pub fn cancel_vesting(
+            ctx: Context<CancelVesting>,
+            proposal_id: u64,
+            owner: Pubkey,
+        ) -> Result<()> {
+            instructions::proposal::cancel_vesting(ctx, proposal_id, owner)
+        }
+
+        pub fn expire_proposal(
+            ctx: Context<ExpireProposal>,
+            proposal_id: u64,
+            owner: Pubkey,
+        ) -> Result<()> {
+            instructions::proposal::expire_proposal(ctx, proposal_id, owner)
+        }
+
+        pub fn vote_on_proposal<'info>(
+            ctx: Context<'_, '_, 'info, 'info, VoteOnProposal<'info>>,
+            proposal_id: u64,
+            owner: Pubkey,
+            choice: VoteChoice,
+            quadratic_votes: Option<u64>,
+        ) -> Result<()> {
+            instructions::proposal::vote_on_proposal(ctx, proposal_id, owner, choice, quadratic_votes)
+        }
 
-    pub fn claim_funds(ctx: Context<ClaimFunds>, proposal_id: u64) -> Result<()> {
-        instructions::vault::claim_funds(ctx, proposal_id)
+        pub fn change_vote(
+            ctx: Context<ChangeVote>,
+            proposal_id: u64,
+            owner: Pubkey,
+            new_choice: VoteChoice,
+        ) -> Result<()> {
+            instructions::proposal::change_vote(ctx, proposal_id, owner, new_choice)
+        }
+
+        pub fn withdraw_vote<'info>(
+            ctx: Context<'_, '_, 'info, 'info, WithdrawVote<'info>>,
+            proposal_id: u64,
+            owner: Pubkey,
+        ) -> Result<()> {
+            instructions::proposal::withdraw_vote(ctx, proposal_id, owner)
+        }
+
+        pub fn delegate_votes(ctx: Context<DelegateVotes>, delegate: Pubkey, category: u8) -> Result<()> {
+            instructions::delegation::delegate_votes(ctx, delegate, category)
+        }
+
+        pub fn revoke_delegation(ctx: Context<RevokeDelegation>, category: u8) -> Result<()> {
+            instructions::delegation::revoke_delegation(ctx, category)
+        }
+
+        pub fn lock_tokens(ctx: Context<LockTokens>, amount: u64, lock_duration: i64) -> Result<()> {
+            instructions::escrow::lock_tokens(ctx, amount, lock_duration)
+        }
+
+        pub fn extend_lock(ctx: Context<ExtendLock>, lock_duration: i64) -> Result<()> {
+            instructions::escrow::extend_lock(ctx, lock_duration)
+        }
+
+        pub fn unlock_tokens(ctx: Context<UnlockTokens>) -> Result<()> {
+            instructions::escrow::unlock_tokens(ctx)
+        }
+
+        #[allow(clippy::too_many_arguments)]
+        pub fn create_round(
+            ctx: Context<CreateRound>,
+            round_id: u64,
+            mint: Option<Pubkey>,
+            voting_mode: VotingMode,
+            budget: u64,
+            application_start: i64,
+            application_end: i64,
+            payout_deadline: i64,
+        ) -> Result<()> {
+            instructions::round::create_round(
+                ctx,
+                round_id,
+                mint,
+                voting_mode,
+                budget,
+                application_start,
+                application_end,
+                payout_deadline,
+            )
+        }
+
+        pub fn deposit_to_vault(
+            ctx: Context<DepositToVault>,
+            round_id: u64,
+            amount: u64,
+            memo: Option<String>,
+        ) -> Result<()> {
+            instructions::vault::deposit_to_vault(ctx, round_id, amount, memo)
+        }
+
+        pub fn deposit_token(ctx: Context<DepositToken>, round_id: u64, amount: u64) -> Result<()> {
+            instructions::vault::deposit_token(ctx, round_id, amount)
+        }
+
+        pub fn finalize_proposal(
+            ctx: Context<FinalizeProposal>,
+            proposal_id: u64,
+            owner: Pubkey,
+        ) -> Result<()> {
+            instructions::vault::finalize_proposal(ctx, proposal_id, owner)
+        }
+
+        pub fn claim_funds(ctx: Context<ClaimFunds>, proposal_id: u64) -> Result<()> {
+            instructions::vault::claim_funds(ctx, proposal_id)
+        }
+
+        pub fn claim_vested(ctx: Context<ClaimVested>, proposal_id: u64) -> Result<()> {
+            instructions::vault::claim_vested(ctx, proposal_id)
+        }
+
+        pub fn claim_token(ctx: Context<ClaimToken>, proposal_id: u64) -> Result<()> {
+            instructions::vault::claim_token(ctx, proposal_id)
+        }
+
+        pub fn ragequit(ctx: Context<Ragequit>, round_id: u64) -> Result<()> {
+            instructions::vault::ragequit(ctx, round_id)
+        }
+
+        pub fn get_spendable_balance(ctx: Context<GetSpendableBalance>, round_id: u64) -> Result<u64> {
+            instructions::vault::get_spendable_balance(ctx, round_id)
+        }
+
+        pub fn reconcile_vault(ctx: Context<ReconcileVault>, round_id: u64) -> Result<()> {
+            instructions::vault::reconcile_vault(ctx, round_id)
+        }
+
+        pub fn set_paused(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+            instructions::emergency::set_paused(ctx, paused)
+        }
+
+        pub fn set_emergency_policy(ctx: Context<SetEmergencyPolicy>, policy: EmergencyPolicy) -> Result<()> {
+            instructions::emergency::set_emergency_policy(ctx, policy)
+        }
+
+        pub fn propose_emergency_withdrawal(
+            ctx: Context<ProposeEmergencyWithdrawal>,
+            round_id: u64,
+            amount: u64,
+            destination: Pubkey,
+            mint: Option<Pubkey>,
+        ) -> Result<()> {
+            instructions::emergency::propose_emergency_withdrawal(ctx, round_id, amount, destination, mint)
+        }
+
+        pub fn approve_emergency_withdrawal(
+            ctx: Context<ApproveEmergencyWithdrawal>,
+            round_id: u64,
+        ) -> Result<()> {
+            instructions::emergency::approve_emergency_withdrawal(ctx, round_id)
+        }
+
+        pub fn execute_emergency_withdrawal(
+            ctx: Context<ExecuteEmergencyWithdrawal>,
+            round_id: u64,
+        ) -> Result<()> {
+            instructions::emergency::execute_emergency_withdrawal(ctx, round_id)
+        }
+
+        pub fn cancel_emergency_withdrawal(
+            ctx: Context<CancelEmergencyWithdrawal>,
+            round_id: u64,
+        ) -> Result<()> {
+            instructions::emergency::cancel_emergency_withdrawal(ctx, round_id)
+        }
     }
 }
+
+pub use anchor_program::*;
@@ -1,15 +1,292 @@
 use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+
+/// Largest admin council a fund can have
+pub const MAX_ADMINS: usize = 10;
+
+/// Independent community fund - every other account is derived under one
+#[account]
+#[derive(InitSpace)]
+pub struct Fund {
+    pub id: u64,
+    pub creator: Pubkey,
+    pub bump: u8,
+}
 
 #[account]
 #[derive(InitSpace)]
 pub struct Config {
-    pub admins: [Pubkey; 3],
+    pub fund: Pubkey,
+    /// Admin council - the account is resized as members join and leave
+    #[max_len(MAX_ADMINS)]
+    pub admins: Vec<Pubkey>,
+    /// Role bitmap of each admin, in the same order as `admins`
+    #[max_len(MAX_ADMINS)]
+    pub roles: Vec<u8>,
+    /// Admin approvals needed for requests at or above the multisig threshold
+    pub admin_threshold: u8,
+    /// Admin change proposals created so far - the next one's id
+    pub admin_change_count: u64,
+    pub governance_mint: Pubkey,
+    pub params: GovernanceParams,
+    pub emergency: EmergencyPolicy,
+    /// Emergency stop - claims and exits are refused, emergency withdrawals allowed
+    pub paused: bool,
     pub bump: u8,
 }
 
+impl Config {
+    pub const MIN_ADMINS: usize = 2;
+
+    /// Rejects spam proposals
+    pub const ROLE_REVIEWER: u8 = 1 << 0;
+    /// Approves funding and moves vault funds
+    pub const ROLE_TREASURER: u8 = 1 << 1;
+    /// Triggers the emergency stop
+    pub const ROLE_PAUSER: u8 = 1 << 2;
+    /// Changes governance and emergency parameters
+    pub const ROLE_PARAMS: u8 = 1 << 3;
+    pub const ALL_ROLES: u8 =
+        Self::ROLE_REVIEWER | Self::ROLE_TREASURER | Self::ROLE_PAUSER | Self::ROLE_PARAMS;
+
+    /// Account size with `admin_count` admins - each holds a key and a role bitmap
+    pub fn space(admin_count: usize) -> usize {
+        8 + Self::INIT_SPACE - MAX_ADMINS.saturating_sub(admin_count) * (32 + 1)
+    }
+
+    fn roles_of(&self, admin: &Pubkey) -> Option<u8> {
+        let index = self.admins.iter().position(|a| a == admin)?;
+        self.roles.get(index).copied()
+    }
+
+    pub fn has_role(&self, admin: &Pubkey, role: u8) -> bool {
+        self.roles_of(admin).is_some_and(|roles| roles & role == role)
+    }
+
+    /// Non-members are unauthorized; members without `role` are refused separately
+    pub fn require_role(&self, admin: &Pubkey, role: u8) -> Result<()> {
+        let roles = self.roles_of(admin).ok_or(ErrorCode::Unauthorized)?;
+        require!(roles & role == role, ErrorCode::MissingRole);
+        Ok(())
+    }
+
+    /// Like `require_role`, but holding any one of the bits in `roles` is enough
+    pub fn require_any_role(&self, admin: &Pubkey, roles: u8) -> Result<()> {
+        let held = self.roles_of(admin).ok_or(ErrorCode::Unauthorized)?;
+        require!(held & roles != 0, ErrorCode::MissingRole);
+        Ok(())
+    }
+
+    /// Members holding `role`
+    pub fn role_count(&self, role: u8) -> usize {
+        self.roles.iter().filter(|roles| *roles & role == role).count()
+    }
+
+    /// Approvals in `approvals` from members still on the council
+    pub fn count_approvals(&self, approvals: &[Pubkey]) -> usize {
+        approvals.iter().filter(|a| self.admins.contains(a)).count()
+    }
+
+    /// Approvals in `approvals` from members who still hold `role`
+    pub fn count_role_approvals(&self, approvals: &[Pubkey], role: u8) -> usize {
+        approvals.iter().filter(|a| self.has_role(a, role)).count()
+    }
+
+    /// The council must stay unique, within bounds, keep every role filled
+    /// and have enough treasurers to reach both thresholds
+    pub fn validate_council(&self) -> Result<()> {
+        let count = self.admins.len();
+        require!(
+            (Self::MIN_ADMINS..=MAX_ADMINS).contains(&count),
+            ErrorCode::InvalidAdminSet
+        );
+        require!(self.roles.len() == count, ErrorCode::InvalidAdminSet);
+        for (i, admin) in self.admins.iter().enumerate() {
+            require!(
+                !self.admins[i + 1..].contains(admin),
+                ErrorCode::InvalidAdminSet
+            );
+        }
+        require!(
+            self.admin_threshold >= 1 && self.admin_threshold as usize <= count,
+            ErrorCode::InvalidAdminSet
+        );
+
+        require!(
+            self.roles.iter().all(|roles| roles & !Self::ALL_ROLES == 0),
+            ErrorCode::InvalidRoles
+        );
+        for role in [
+            Self::ROLE_REVIEWER,
+            Self::ROLE_TREASURER,
+            Self::ROLE_PAUSER,
+            Self::ROLE_PARAMS,
+        ] {
+            require!(self.role_count(role) > 0, ErrorCode::InvalidRoles);
+        }
+
+        // Large grants and emergency withdrawals are approved by treasurers only
+        let treasurers = self.role_count(Self::ROLE_TREASURER);
+        require!(
+            self.admin_threshold as usize <= treasurers,
+            ErrorCode::InvalidRoles
+        );
+        self.emergency.validate(treasurers)
+    }
+
+    /// Hands `old_admin`'s slot and roles to `new_admin`; the result must still be a valid council
+    pub fn replace_admin(&mut self, old_admin: Pubkey, new_admin: Pubkey) -> Result<()> {
+        let slot = self
+            .admins
+            .iter_mut()
+            .find(|a| **a == old_admin)
+            .ok_or(ErrorCode::Unauthorized)?;
+        *slot = new_admin;
+        self.validate_council()
+    }
+
+    /// Applies an approved membership change; the result must still be a valid council
+    pub fn apply_admin_change(&mut self, change: &AdminChange) -> Result<()> {
+        match *change {
+            AdminChange::Add { admin, roles } => {
+                self.admins.push(admin);
+                self.roles.push(roles);
+            }
+            AdminChange::Remove { admin } => {
+                let index = self
+                    .admins
+                    .iter()
+                    .position(|a| *a == admin)
+                    .ok_or(ErrorCode::InvalidAdminSet)?;
+                self.admins.remove(index);
+                self.roles.remove(index);
+            }
+            AdminChange::SetThreshold { threshold } => self.admin_threshold = threshold,
+            AdminChange::SetRoles { admin, roles } => {
+                let index = self
+                    .admins
+                    .iter()
+                    .position(|a| *a == admin)
+                    .ok_or(ErrorCode::InvalidAdminSet)?;
+                self.roles[index] = roles;
+            }
+        }
+        self.validate_council()
+    }
+}
+
+/// Treasurer approvals and waiting time an emergency withdrawal needs
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct EmergencyPolicy {
+    /// Approvals needed, counting the proposer's
+    pub threshold: u8,
+    /// Seconds between proposing a withdrawal and executing it
+    pub timelock: i64,
+}
+
+impl EmergencyPolicy {
+    #[cfg(not(feature = "local-testing"))]
+    pub const MIN_TIMELOCK: i64 = 3600;
+    /// Local validators can't skip ahead in time, so tests wait out a timelock of seconds
+    #[cfg(feature = "local-testing")]
+    pub const MIN_TIMELOCK: i64 = 2;
+    pub const MAX_TIMELOCK: i64 = 30 * 86400;
+
+    pub const DEFAULT: Self = Self {
+        threshold: 2,
+        timelock: 2 * 86400,
+    };
+
+    /// A single treasurer can never move funds alone
+    pub fn validate(&self, treasurer_count: usize) -> Result<()> {
+        require!(
+            self.threshold >= 2 && self.threshold as usize <= treasurer_count,
+            ErrorCode::InvalidEmergencyPolicy
+        );
+        require!(
+            (Self::MIN_TIMELOCK..=Self::MAX_TIMELOCK).contains(&self.timelock),
+            ErrorCode::InvalidEmergencyPolicy
+        );
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct GovernanceParams {
+    /// Length of a round's voting window after applications close, in seconds.
+    /// Rounds fix their `voting_end` from it when created; proposals vote until
+    /// their round's `voting_end`, so changing it never moves an existing round.
+    pub voting_period: i64,
+    /// Minimum turnout (yes + no + abstain) for a token-weighted proposal to pass,
+    /// in governance token base units
+    pub min_quorum: u64,
+    /// Minimum turnout for a quadratic proposal to pass, in effective votes
+    pub min_quadratic_quorum: u64,
+    /// Share of yes over yes + no needed to pass, in basis points
+    pub approval_threshold_bps: u16,
+    /// Requests at or above this many lamports need the council's admin threshold of approvals
+    pub multisig_threshold: u64,
+}
+
+impl GovernanceParams {
+    #[cfg(not(feature = "local-testing"))]
+    pub const MIN_VOTING_PERIOD: i64 = 3600;
+    /// Local validators can't skip ahead in time, so tests vote for seconds
+    #[cfg(feature = "local-testing")]
+    pub const MIN_VOTING_PERIOD: i64 = 2;
+    pub const MAX_VOTING_PERIOD: i64 = 90 * 86400;
+
+    // One ROLE_PARAMS admin sets these alone, so they may only be tightened past
+    // the defaults - never loosened into single-approver payouts or token quorums
+    pub const MIN_QUORUM: u64 = 100;
+    pub const MIN_QUADRATIC_QUORUM: u64 = 10;
+    /// Passing always takes at least a simple majority
+    pub const MIN_APPROVAL_BPS: u16 = 5_000;
+    /// Highest amount a single treasurer may approve below
+    pub const MAX_MULTISIG_THRESHOLD: u64 = 1_000_000_000_000;
+
+    pub const DEFAULT: Self = Self {
+        voting_period: 604800,
+        min_quorum: 100,
+        min_quadratic_quorum: 10,
+        approval_threshold_bps: 5_000,
+        multisig_threshold: 1_000_000_000_000, // 1000 SOL in lamports
+    };
+
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            (Self::MIN_VOTING_PERIOD..=Self::MAX_VOTING_PERIOD).contains(&self.voting_period),
+            ErrorCode::InvalidGovernanceParams
+        );
+        require!(
+            self.min_quorum >= Self::MIN_QUORUM
+                && self.min_quadratic_quorum >= Self::MIN_QUADRATIC_QUORUM,
+            ErrorCode::InvalidGovernanceParams
+        );
+        require!(
+            (Self::MIN_APPROVAL_BPS..=10_000).contains(&self.approval_threshold_bps),
+            ErrorCode::InvalidGovernanceParams
+        );
+        require!(
+            (1..=Self::MAX_MULTISIG_THRESHOLD).contains(&self.multisig_threshold),
+            ErrorCode::InvalidGovernanceParams
+        );
+        Ok(())
+    }
+
+    /// Turnout a proposal needs - token weight and quadratic votes aren't comparable
+    pub fn quorum_for(&self, mode: VotingMode) -> u64 {
+        match mode {
+            VotingMode::TokenWeighted => self.min_quorum,
+            VotingMode::Quadratic => self.min_quadratic_quorum,
+        }
+    }
+}
+
 #[account]
 #[derive(InitSpace)]
 pub struct Proposal {
+    pub fund: Pubkey,
     pub id: u64,
     pub owner: Pubkey,
     #[max_len(50)]
@@ -17,24 +294,268 @@ pub struct Proposal {
     #[max_len(200)]
     pub description: String,
     pub amount_requested: u64,
+    pub category: u8,
+    pub voting_mode: VotingMode,
     pub status: ProposalStatus,
     pub created_at: i64,
-    pub vote_count: u64,
+    pub yes_votes: u64,
+    pub no_votes: u64,
+    pub abstain_votes: u64,
     pub bump: u8,
 
-    #[max_len(3)]
+    #[max_len(MAX_ADMINS)]
     pub funding_approvals: Vec<Pubkey>,
 
     pub finalized_at: i64,
+
+    /// Parameters in force when the proposal was created. The voting window
+    /// comes from the round (`voting_end`), not from `params.voting_period`.
+    pub params: GovernanceParams,
+
+    /// Tranches summing to amount_requested; empty means one lump-sum claim
+    #[max_len(5)]
+    pub milestones: Vec<Milestone>,
+
+    /// Linear release of amount_requested instead of a lump sum
+    pub vesting: Option<VestingSchedule>,
+    /// When admins stopped further vesting (0 = never)
+    pub vesting_revoked_at: i64,
+    /// Paid out so far
+    pub amount_claimed: u64,
+
+    /// Asset being requested - None for SOL, otherwise an SPL mint
+    pub requested_mint: Option<Pubkey>,
+
+    /// Funding round this proposal applied to
+    pub round_id: u64,
+    /// Voting closes with the round's voting window
+    pub voting_end: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct VestingSchedule {
+    pub start: i64,
+    /// Nothing is claimable before the cliff
+    pub cliff: i64,
+    pub end: i64,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct Milestone {
+    pub amount: u64,
+    #[max_len(100)]
+    pub description: String,
+    pub status: MilestoneStatus,
+
+    #[max_len(MAX_ADMINS)]
+    pub approvals: Vec<Pubkey>,
+}
+
+impl Milestone {
+    pub const MAX_MILESTONES: usize = 5;
+    pub const MAX_DESCRIPTION_LEN: usize = 100;
+}
+
+/// Milestone as supplied to create_proposal
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct MilestoneInput {
+    pub amount: u64,
+    pub description: String,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum MilestoneStatus {
+    Pending,
+    Submitted,
+    Approved,
+    Claimed,
+}
+
+impl Proposal {
+    /// Total the owner can ever receive - less than requested if vesting was cancelled
+    pub fn final_payout(&self) -> u64 {
+        if self.vesting.is_some() {
+            self.vested_amount(i64::MAX)
+        } else {
+            self.amount_requested
+        }
+    }
+
+    /// Round budget still reserved for this proposal
+    pub fn outstanding_allocation(&self) -> u64 {
+        match self.status {
+            ProposalStatus::Finalized | ProposalStatus::Approved => {
+                self.final_payout() - self.amount_claimed
+            }
+            _ => 0,
+        }
+    }
+
+    /// Amount at or above which approving this grant takes the council's admin threshold -
+    /// the lamport threshold snapshotted at creation for SOL grants, the mint's own
+    /// threshold for token grants
+    pub fn multisig_threshold(&self, ledger: Option<&TokenLedger>) -> Result<u64> {
+        match (self.requested_mint, ledger) {
+            (None, _) => Ok(self.params.multisig_threshold),
+            (Some(_), Some(ledger)) => Ok(ledger.multisig_threshold),
+            (Some(_), None) => err!(ErrorCode::TokenLedgerRequired),
+        }
+    }
+
+    /// Vault balance still reserved for this proposal - lamports for SOL grants,
+    /// `requested_mint` tokens otherwise. Only approved grants hold a reservation.
+    pub fn outstanding_commitment(&self) -> u64 {
+        if self.status == ProposalStatus::Approved {
+            self.final_payout() - self.amount_claimed
+        } else {
+            0
+        }
+    }
+
+    /// Turnout - every vote cast, including abstentions
+    pub fn total_votes(&self) -> u64 {
+        self.yes_votes + self.no_votes + self.abstain_votes
+    }
+
+    /// Amount unlocked at `now`, frozen at the revocation time if vesting was cancelled
+    pub fn vested_amount(&self, now: i64) -> u64 {
+        let Some(vesting) = self.vesting else {
+            return 0;
+        };
+        let now = if self.vesting_revoked_at > 0 {
+            now.min(self.vesting_revoked_at)
+        } else {
+            now
+        };
+
+        if now < vesting.cliff {
+            0
+        } else if now >= vesting.end {
+            self.amount_requested
+        } else {
+            let elapsed = (now - vesting.start) as u128;
+            let duration = (vesting.end - vesting.start) as u128;
+            (self.amount_requested as u128 * elapsed / duration) as u64
+        }
+    }
+
+    /// What the owner can claim right now: vested, approved milestones, or the lump sum
+    pub fn claimable_amount(&self, now: i64) -> u64 {
+        if self.vesting.is_some() {
+            self.vested_amount(now) - self.amount_claimed
+        } else if self.milestones.is_empty() {
+            self.amount_requested - self.amount_claimed
+        } else {
+            self.milestones
+                .iter()
+                .filter(|milestone| milestone.status == MilestoneStatus::Approved)
+                .map(|milestone| milestone.amount)
+                .sum()
+        }
+    }
+
+    /// Books a payout of `claimable_amount(now)`; the proposal is Claimed once nothing more is owed
+    pub fn record_claim(&mut self, payout: u64) -> Result<()> {
+        self.amount_claimed += payout;
+        for milestone in self.milestones.iter_mut() {
+            if milestone.status == MilestoneStatus::Approved {
+                milestone.status = MilestoneStatus::Claimed;
+            }
+        }
+
+        let fully_claimed = if self.vesting.is_some() {
+            self.amount_claimed == self.final_payout()
+        } else {
+            self.milestones
+                .iter()
+                .all(|milestone| milestone.status == MilestoneStatus::Claimed)
+        };
+        if fully_claimed {
+            self.transition_to(ProposalStatus::Claimed)?;
+        }
+        Ok(())
+    }
+
+    /// Freezes vesting at `now` and returns the unvested remainder.
+    /// The grant closes if nothing vested is left for the owner to collect.
+    pub fn revoke_vesting(&mut self, now: i64) -> Result<u64> {
+        self.vesting_revoked_at = now;
+        let vested = self.vested_amount(now);
+        if vested == self.amount_claimed {
+            if vested > 0 {
+                self.transition_to(ProposalStatus::Claimed)?;
+            } else {
+                self.transition_to(ProposalStatus::Cancelled)?;
+            }
+        }
+        Ok(self.amount_requested - vested)
+    }
+
+    pub fn transition_to(&mut self, next: ProposalStatus) -> Result<()> {
+        require!(
+            self.status.can_transition_to(next),
+            ErrorCode::InvalidStatusTransition
+        );
+        self.status = next;
+        Ok(())
+    }
+
+    pub fn add_vote(&mut self, choice: VoteChoice, weight: u64) {
+        match choice {
+            VoteChoice::Yes => self.yes_votes += weight,
+            VoteChoice::No => self.no_votes += weight,
+            VoteChoice::Abstain => self.abstain_votes += weight,
+        }
+    }
+
+    pub fn remove_vote(&mut self, choice: VoteChoice, weight: u64) {
+        match choice {
+            VoteChoice::Yes => self.yes_votes -= weight,
+            VoteChoice::No => self.no_votes -= weight,
+            VoteChoice::Abstain => self.abstain_votes -= weight,
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum VotingMode {
+    /// Weight comes from locked governance tokens
+    TokenWeighted,
+    /// n effective votes cost n² voice credits
+    Quadratic,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
 pub enum ProposalStatus {
     Pending,
     Finalized,
     Approved,
     Rejected,
     Claimed,
+    Cancelled,
+    /// Payout deadline passed before the grant was paid in full
+    Expired,
+}
+
+impl ProposalStatus {
+    /// The only allowed moves: Pending -> Finalized -> Approved -> Claimed,
+    /// with Rejected, Cancelled and Expired as exits before funds are claimed
+    pub fn can_transition_to(&self, next: ProposalStatus) -> bool {
+        use ProposalStatus::*;
+        matches!(
+            (self, next),
+            (Pending, Finalized)
+                | (Pending, Rejected)
+                | (Pending, Cancelled)
+                | (Finalized, Approved)
+                | (Finalized, Rejected)
+                | (Finalized, Cancelled)
+                | (Finalized, Expired)
+                | (Approved, Claimed)
+                | (Approved, Cancelled)
+                | (Approved, Expired)
+        )
+    }
 }
 
 #[account]
@@ -48,14 +569,792 @@ pub struct UserProfile {
 #[derive(InitSpace)]
 pub struct VoteAccount {
     pub timestamp: i64,
+    /// Effective votes added to the tally
     pub token_weight: u64,
+    /// Voice credits paid for a quadratic vote (0 for token-weighted votes)
+    pub credits_spent: u64,
+    /// Part of token_weight that was delegated to this voter
+    pub delegated_weight: u64,
+    /// Delegators whose receipts were written with this vote
+    pub delegator_count: u32,
+    pub choice: VoteChoice,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum VoteChoice {
+    Yes,
+    No,
+    Abstain,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct VoterLock {
+    pub fund: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub lock_start: i64,
+    pub lock_end: i64,
+    pub bump: u8,
+    pub escrow_bump: u8,
+}
+
+impl VoterLock {
+    /// Shortest lock accepted - one week
+    #[cfg(not(feature = "local-testing"))]
+    pub const MIN_LOCK_DURATION: i64 = 604800;
+    /// Local validators can't skip ahead in time, so tests lock for seconds
+    #[cfg(feature = "local-testing")]
+    pub const MIN_LOCK_DURATION: i64 = 2;
+    /// Longest lock (4 years) - a lock this long votes with its full amount
+    pub const MAX_LOCK_DURATION: i64 = 4 * 365 * 86400;
+
+    /// ve-style weight: locked amount scaled by the remaining lock time
+    pub fn voting_power(&self, now: i64) -> u64 {
+        let remaining = self
+            .lock_end
+            .saturating_sub(now)
+            .clamp(0, Self::MAX_LOCK_DURATION);
+        (self.amount as u128 * remaining as u128 / Self::MAX_LOCK_DURATION as u128) as u64
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct VoiceCredits {
+    pub owner: Pubkey,
+    pub balance: u64,
+    pub bump: u8,
+}
+
+impl VoiceCredits {
+    /// Most credits one voter can hold - a single params admin grants them
+    pub const MAX_BALANCE: u64 = 1_000;
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Delegation {
+    pub fund: Pubkey,
+    pub delegator: Pubkey,
+    pub delegate: Pubkey,
+    pub category: u8,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+impl Delegation {
+    /// Category value for a delegation that covers every proposal
+    pub const ALL_CATEGORIES: u8 = u8::MAX;
+
+    pub fn covers(&self, category: u8) -> bool {
+        self.category == Self::ALL_CATEGORIES || self.category == category
+    }
+}
+
+/// Per-proposal marker that a delegator's weight has been cast by a delegate
+#[account]
+#[derive(InitSpace)]
+pub struct DelegationReceipt {
+    pub delegator: Pubkey,
+    pub delegate: Pubkey,
+    pub weight: u64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Round {
+    pub fund: Pubkey,
+    pub id: u64,
+    /// Asset the round pays out - None for SOL, otherwise an SPL mint
+    pub mint: Option<Pubkey>,
+    /// How every proposal in the round is voted on
+    pub voting_mode: VotingMode,
+    pub budget: u64,
+    /// Budget reserved by proposals that passed the vote
+    pub allocated: u64,
+    pub application_start: i64,
+    pub application_end: i64,
+    pub voting_end: i64,
+    /// Claims against the round are refused after this time
+    pub payout_deadline: i64,
     pub bump: u8,
 }
 
+impl Round {
+    pub fn is_accepting_applications(&self, now: i64) -> bool {
+        (self.application_start..self.application_end).contains(&now)
+    }
+
+    /// Reserves `amount` of the budget; false if it doesn't fit
+    pub fn allocate(&mut self, amount: u64) -> bool {
+        match self.allocated.checked_add(amount) {
+            Some(allocated) if allocated <= self.budget => {
+                self.allocated = allocated;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn release(&mut self, amount: u64) {
+        self.allocated = self.allocated.saturating_sub(amount);
+    }
+}
+
+/// One per round - holds the round's SOL and signs for its token accounts
 #[account]
 #[derive(InitSpace)]
 pub struct Vault {
     pub total_deposited: u64,
     pub total_claimed: u64,
+    /// Lamports reserved for approved grants that haven't been claimed yet
+    pub committed: u64,
+    /// Contributions given up by depositors who ragequit
+    pub total_burned: u64,
+    /// Lamports paid back to depositors who ragequit
+    pub total_withdrawn: u64,
+    /// Lamports that arrived without going through deposit_to_vault
+    pub external_donations: u64,
+    /// Lamports moved out by emergency withdrawals
+    pub emergency_withdrawn: u64,
+    /// Depositor shares outstanding - each is a claim on the uncommitted balance
+    pub total_shares: u64,
     pub bump: u8,
 }
+
+impl Vault {
+    /// Shares a deposit of `amount` buys when the uncommitted balance is `available`.
+    /// Priced at the current value per share, so donations and surplus already in
+    /// the vault stay with the existing depositors. The first deposit, or one into
+    /// a vault with nothing left to share, gets one share per lamport.
+    pub fn shares_for_deposit(&self, amount: u64, available: u64) -> u64 {
+        if self.total_shares == 0 || available == 0 {
+            return amount;
+        }
+        (amount as u128 * self.total_shares as u128 / available as u128) as u64
+    }
+
+    /// Lamports `shares` redeem for out of the uncommitted balance `available`
+    pub fn payout_for_shares(&self, shares: u64, available: u64) -> u64 {
+        if self.total_shares == 0 {
+            return 0;
+        }
+        (available as u128 * shares as u128 / self.total_shares as u128) as u64
+    }
+
+    /// Lamports above the rent-exempt minimum - the account itself must never drop below it
+    pub fn spendable_lamports(vault_info: &AccountInfo) -> Result<u64> {
+        let reserve = Rent::get()?.minimum_balance(vault_info.data_len());
+        Ok(vault_info.lamports().saturating_sub(reserve))
+    }
+
+    /// Balance the recorded totals account for, on top of the rent-exempt reserve
+    pub fn expected_lamports(&self, reserve: u64) -> u64 {
+        (reserve + self.total_deposited + self.external_donations)
+            .saturating_sub(self.total_claimed + self.total_withdrawn + self.emergency_withdrawn)
+    }
+
+    /// Part of `balance` not yet promised to approved grants
+    pub fn free_balance(&self, balance: u64) -> u64 {
+        balance.saturating_sub(self.committed)
+    }
+
+    /// Reserves `amount` for a grant. Returns false if the free balance can't cover it.
+    pub fn commit(&mut self, amount: u64, balance: u64) -> bool {
+        if amount > self.free_balance(balance) {
+            return false;
+        }
+        self.committed += amount;
+        true
+    }
+
+    pub fn release(&mut self, amount: u64) {
+        self.committed = self.committed.saturating_sub(amount);
+    }
+
+    /// Releases a grant's reservation from the vault for SOL grants, or from the
+    /// token ledger of `requested_mint` for token grants. The ledger is only
+    /// needed when there is something to release.
+    pub fn release_grant(
+        &mut self,
+        ledger: Option<&mut TokenLedger>,
+        requested_mint: Option<Pubkey>,
+        amount: u64,
+    ) -> Result<()> {
+        if requested_mint.is_none() {
+            self.release(amount);
+        } else if amount > 0 {
+            ledger.ok_or(ErrorCode::TokenLedgerRequired)?.release(amount);
+        }
+        Ok(())
+    }
+}
+
+/// Pending move of a vault's SOL, only possible while the program is paused
+#[account]
+#[derive(InitSpace)]
+pub struct EmergencyWithdrawal {
+    pub vault: Pubkey,
+    pub proposer: Pubkey,
+    /// Receives the lamports, or owns the token account that receives the tokens
+    pub destination: Pubkey,
+    /// Token to withdraw from the vault's token account, None for lamports
+    pub mint: Option<Pubkey>,
+    pub amount: u64,
+    #[max_len(MAX_ADMINS)]
+    pub approvals: Vec<Pubkey>,
+    /// Policy threshold when proposed - later policy changes don't apply
+    pub threshold: u8,
+    pub executable_at: i64,
+    pub bump: u8,
+}
+
+/// Change to the admin council, applied once enough admins approve it.
+/// Handing over a seat goes through `AdminNomination` instead.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum AdminChange {
+    Add { admin: Pubkey, roles: u8 },
+    Remove { admin: Pubkey },
+    SetThreshold { threshold: u8 },
+    SetRoles { admin: Pubkey, roles: u8 },
+}
+
+impl AdminChange {
+    /// Council size once this change is applied
+    pub fn admin_count_after(&self, admin_count: usize) -> usize {
+        match self {
+            AdminChange::Add { .. } => admin_count + 1,
+            AdminChange::Remove { .. } => admin_count.saturating_sub(1),
+            AdminChange::SetThreshold { .. } | AdminChange::SetRoles { .. } => admin_count,
+        }
+    }
+}
+
+/// Pending admin council change collecting approvals
+#[account]
+#[derive(InitSpace)]
+pub struct AdminChangeProposal {
+    pub fund: Pubkey,
+    pub id: u64,
+    pub proposer: Pubkey,
+    pub change: AdminChange,
+    #[max_len(MAX_ADMINS)]
+    pub approvals: Vec<Pubkey>,
+    /// Can't be approved or executed after this time
+    pub expires_at: i64,
+    pub bump: u8,
+}
+
+impl AdminChangeProposal {
+    /// How long a change stays open for approvals
+    pub const APPROVAL_WINDOW: i64 = 7 * 86400;
+
+    pub fn is_expired(&self, current_time: i64) -> bool {
+        current_time > self.expires_at
+    }
+}
+
+/// Admin's pending handover of their seat - takes effect once the nominee accepts
+#[account]
+#[derive(InitSpace)]
+pub struct AdminNomination {
+    pub fund: Pubkey,
+    pub admin: Pubkey,
+    pub nominee: Pubkey,
+    pub nominated_at: i64,
+    pub bump: u8,
+}
+
+/// One depositor's contributions to a round's vault
+#[account]
+#[derive(InitSpace)]
+pub struct DepositorRecord {
+    pub vault: Pubkey,
+    pub depositor: Pubkey,
+    pub total_contributed: u64,
+    /// Vault shares bought with the contributions - redeemed on ragequit
+    pub shares: u64,
+    pub deposit_count: u64,
+    pub first_deposit_at: i64,
+    pub last_deposit_at: i64,
+    /// Memo attached to the most recent deposit, empty if none was given
+    #[max_len(64)]
+    pub last_memo: String,
+    pub bump: u8,
+}
+
+impl DepositorRecord {
+    pub const MAX_MEMO_LEN: usize = 64;
+}
+
+/// Per-mint accounting for SPL tokens held by the vault
+#[account]
+#[derive(InitSpace)]
+pub struct TokenLedger {
+    pub mint: Pubkey,
+    pub total_deposited: u64,
+    pub total_claimed: u64,
+    /// Tokens moved out by executed emergency withdrawals
+    pub emergency_withdrawn: u64,
+    /// Tokens reserved for approved grants that haven't been claimed yet
+    pub committed: u64,
+    /// Token grants at or above this many base units need the council's admin threshold
+    /// of approvals. Zero until a params admin sets it, so every grant does.
+    pub multisig_threshold: u64,
+    pub bump: u8,
+    /// Bump of the vault's token account for this mint
+    pub token_account_bump: u8,
+}
+
+impl TokenLedger {
+    /// Share of the mint's deposits a single treasurer may approve below, in basis points
+    pub const MAX_THRESHOLD_BPS: u64 = 1_000;
+
+    /// Highest multisig threshold a params admin may set - decimals differ per mint,
+    /// so the bound follows what was deposited rather than a fixed amount
+    pub fn max_multisig_threshold(&self) -> u64 {
+        (self.total_deposited as u128 * Self::MAX_THRESHOLD_BPS as u128 / 10_000) as u64
+    }
+
+    /// Part of the token account's `balance` not yet promised to approved grants
+    pub fn free_balance(&self, balance: u64) -> u64 {
+        balance.saturating_sub(self.committed)
+    }
+
+    /// Reserves `amount` for a grant. Returns false if the free balance can't cover it.
+    pub fn commit(&mut self, amount: u64, balance: u64) -> bool {
+        if amount > self.free_balance(balance) {
+            return false;
+        }
+        self.committed += amount;
+        true
+    }
+
+    pub fn release(&mut self, amount: u64) {
+        self.committed = self.committed.saturating_sub(amount);
+    }
+
+    /// Books an emergency withdrawal that left `remaining` tokens in the vault. Grants
+    /// can't stay reserved against tokens that are gone.
+    pub fn record_emergency_withdrawal(&mut self, amount: u64, remaining: u64) {
+        self.emergency_withdrawn += amount;
+        self.committed = self.committed.min(remaining);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AMOUNT: u64 = 1_000;
+
+    fn grant(status: ProposalStatus) -> Proposal {
+        Proposal {
+            fund: Pubkey::new_unique(),
+            id: 0,
+            owner: Pubkey::new_unique(),
+            title: String::new(),
+            description: String::new(),
+            amount_requested: AMOUNT,
+            category: 0,
+            voting_mode: VotingMode::TokenWeighted,
+            status,
+            created_at: 0,
+            yes_votes: 0,
+            no_votes: 0,
+            abstain_votes: 0,
+            bump: 0,
+            funding_approvals: Vec::new(),
+            finalized_at: 0,
+            params: GovernanceParams::DEFAULT,
+            milestones: Vec::new(),
+            vesting: None,
+            vesting_revoked_at: 0,
+            amount_claimed: 0,
+            requested_mint: None,
+            round_id: 0,
+            voting_end: 0,
+        }
+    }
+
+    /// Vests linearly over [100, 200] with a cliff at 150
+    fn vesting_grant() -> Proposal {
+        let mut proposal = grant(ProposalStatus::Approved);
+        proposal.vesting = Some(VestingSchedule {
+            start: 100,
+            cliff: 150,
+            end: 200,
+        });
+        proposal
+    }
+
+    #[test]
+    fn status_follows_the_grant_lifecycle() {
+        use ProposalStatus::*;
+        assert!(Pending.can_transition_to(Finalized));
+        assert!(Finalized.can_transition_to(Approved));
+        assert!(Approved.can_transition_to(Claimed));
+
+        // Stages can't be skipped
+        assert!(!Pending.can_transition_to(Approved));
+        assert!(!Pending.can_transition_to(Claimed));
+        assert!(!Finalized.can_transition_to(Claimed));
+        assert!(!Pending.can_transition_to(Expired));
+    }
+
+    #[test]
+    fn exits_close_the_proposal_before_payout() {
+        use ProposalStatus::*;
+        for from in [Pending, Finalized] {
+            assert!(from.can_transition_to(Rejected));
+            assert!(from.can_transition_to(Cancelled));
+        }
+        assert!(Approved.can_transition_to(Cancelled));
+        assert!(Finalized.can_transition_to(Expired));
+        assert!(Approved.can_transition_to(Expired));
+        assert!(!Approved.can_transition_to(Rejected));
+
+        // Terminal states stay terminal
+        let all = [Pending, Finalized, Approved, Rejected, Claimed, Cancelled, Expired];
+        for from in [Rejected, Claimed, Cancelled, Expired] {
+            for next in all {
+                assert!(!from.can_transition_to(next));
+            }
+        }
+    }
+
+    #[test]
+    fn nothing_vests_before_the_cliff() {
+        let proposal = vesting_grant();
+        assert_eq!(proposal.vested_amount(100), 0);
+        assert_eq!(proposal.vested_amount(149), 0);
+        assert_eq!(proposal.claimable_amount(149), 0);
+    }
+
+    #[test]
+    fn vesting_is_linear_from_start_once_past_the_cliff() {
+        let mut proposal = vesting_grant();
+        assert_eq!(proposal.vested_amount(150), AMOUNT / 2);
+        assert_eq!(proposal.vested_amount(175), AMOUNT * 3 / 4);
+
+        proposal.record_claim(AMOUNT / 2).unwrap();
+        assert_eq!(proposal.claimable_amount(175), AMOUNT / 4);
+        assert!(proposal.status == ProposalStatus::Approved);
+    }
+
+    #[test]
+    fn everything_vests_at_the_end() {
+        let mut proposal = vesting_grant();
+        assert_eq!(proposal.vested_amount(200), AMOUNT);
+        assert_eq!(proposal.vested_amount(i64::MAX), AMOUNT);
+        assert_eq!(proposal.final_payout(), AMOUNT);
+
+        proposal.record_claim(proposal.claimable_amount(250)).unwrap();
+        assert_eq!(proposal.amount_claimed, AMOUNT);
+        assert!(proposal.status == ProposalStatus::Claimed);
+    }
+
+    #[test]
+    fn revoking_mid_vesting_freezes_the_payout() {
+        let mut proposal = vesting_grant();
+        proposal.record_claim(proposal.claimable_amount(160)).unwrap();
+        assert_eq!(proposal.amount_claimed, 600);
+
+        let unvested = proposal.revoke_vesting(180).unwrap();
+        assert_eq!(unvested, 200);
+        assert_eq!(proposal.final_payout(), 800);
+        assert_eq!(proposal.vested_amount(i64::MAX), 800);
+        assert_eq!(proposal.outstanding_commitment(), 200);
+
+        // The vested rest stays claimable, then the grant closes
+        assert!(proposal.status == ProposalStatus::Approved);
+        assert_eq!(proposal.claimable_amount(500), 200);
+        proposal.record_claim(200).unwrap();
+        assert!(proposal.status == ProposalStatus::Claimed);
+    }
+
+    #[test]
+    fn revoking_after_claiming_everything_vested_closes_as_claimed() {
+        let mut proposal = vesting_grant();
+        proposal.record_claim(proposal.claimable_amount(160)).unwrap();
+
+        assert_eq!(proposal.revoke_vesting(160).unwrap(), 400);
+        assert!(proposal.status == ProposalStatus::Claimed);
+    }
+
+    #[test]
+    fn revoking_before_the_cliff_cancels_the_grant() {
+        let mut proposal = vesting_grant();
+
+        assert_eq!(proposal.revoke_vesting(120).unwrap(), AMOUNT);
+        assert_eq!(proposal.final_payout(), 0);
+        assert!(proposal.status == ProposalStatus::Cancelled);
+    }
+
+    fn milestone(amount: u64, status: MilestoneStatus) -> Milestone {
+        Milestone {
+            amount,
+            description: String::new(),
+            status,
+            approvals: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn only_approved_milestones_are_claimable() {
+        let mut proposal = grant(ProposalStatus::Approved);
+        proposal.milestones = vec![
+            milestone(300, MilestoneStatus::Approved),
+            milestone(300, MilestoneStatus::Submitted),
+            milestone(400, MilestoneStatus::Pending),
+        ];
+        assert_eq!(proposal.claimable_amount(0), 300);
+
+        proposal.record_claim(300).unwrap();
+        assert!(proposal.milestones[0].status == MilestoneStatus::Claimed);
+        assert!(proposal.milestones[1].status == MilestoneStatus::Submitted);
+        assert_eq!(proposal.claimable_amount(0), 0);
+        assert!(proposal.status == ProposalStatus::Approved);
+    }
+
+    #[test]
+    fn claiming_the_last_milestone_closes_the_grant() {
+        let mut proposal = grant(ProposalStatus::Approved);
+        proposal.milestones = vec![
+            milestone(600, MilestoneStatus::Claimed),
+            milestone(400, MilestoneStatus::Approved),
+        ];
+        proposal.amount_claimed = 600;
+
+        let payout = proposal.claimable_amount(0);
+        assert_eq!(payout, 400);
+        proposal.record_claim(payout).unwrap();
+        assert_eq!(proposal.amount_claimed, AMOUNT);
+        assert!(proposal.milestones.iter().all(|m| m.status == MilestoneStatus::Claimed));
+        assert!(proposal.status == ProposalStatus::Claimed);
+    }
+
+    fn ledger() -> TokenLedger {
+        TokenLedger {
+            mint: Pubkey::new_unique(),
+            total_deposited: 0,
+            total_claimed: 0,
+            emergency_withdrawn: 0,
+            committed: 0,
+            multisig_threshold: 0,
+            bump: 0,
+            token_account_bump: 0,
+        }
+    }
+
+    #[test]
+    fn token_grants_reserve_against_the_token_balance() {
+        let mut proposal = grant(ProposalStatus::Approved);
+        proposal.requested_mint = Some(Pubkey::new_unique());
+        assert_eq!(proposal.outstanding_commitment(), AMOUNT);
+
+        let mut ledger = ledger();
+        assert!(ledger.commit(AMOUNT, AMOUNT));
+        // The balance is fully promised, a second grant can't be approved
+        assert!(!ledger.commit(1, AMOUNT));
+        assert_eq!(ledger.free_balance(AMOUNT), 0);
+
+        ledger.release(400);
+        assert_eq!(ledger.free_balance(AMOUNT), 400);
+    }
+
+    #[test]
+    fn token_grants_use_their_mint_threshold() {
+        let mut ledger = ledger();
+        ledger.multisig_threshold = 500;
+
+        let mut proposal = grant(ProposalStatus::Finalized);
+        // SOL grants keep the lamport threshold, whatever ledger comes along
+        assert_eq!(
+            proposal.multisig_threshold(Some(&ledger)).unwrap(),
+            GovernanceParams::DEFAULT.multisig_threshold
+        );
+
+        proposal.requested_mint = Some(ledger.mint);
+        assert_eq!(proposal.multisig_threshold(Some(&ledger)).unwrap(), 500);
+        assert_eq!(
+            proposal.multisig_threshold(None).unwrap_err(),
+            ErrorCode::TokenLedgerRequired.into()
+        );
+    }
+
+    #[test]
+    fn token_threshold_is_bounded_by_deposits() {
+        let mut ledger = ledger();
+        assert_eq!(ledger.max_multisig_threshold(), 0);
+
+        ledger.total_deposited = 50_000;
+        assert_eq!(ledger.max_multisig_threshold(), 5_000);
+    }
+
+    #[test]
+    fn emergency_withdrawals_shrink_token_reservations() {
+        let mut ledger = ledger();
+        assert!(ledger.commit(600, AMOUNT));
+
+        // Free tokens go first, the reservation is untouched
+        ledger.record_emergency_withdrawal(400, AMOUNT - 400);
+        assert_eq!(ledger.committed, 600);
+
+        // Past that, grants can only stay reserved against what's left
+        ledger.record_emergency_withdrawal(500, 100);
+        assert_eq!(ledger.committed, 100);
+        assert_eq!(ledger.emergency_withdrawn, 900);
+    }
+
+    #[test]
+    fn releasing_a_token_grant_goes_to_its_ledger() {
+        let mint = Some(Pubkey::new_unique());
+        let mut vault = Vault {
+            total_deposited: 0,
+            total_claimed: 0,
+            committed: AMOUNT,
+            total_burned: 0,
+            total_withdrawn: 0,
+            external_donations: 0,
+            emergency_withdrawn: 0,
+            total_shares: 0,
+            bump: 0,
+        };
+        let mut ledger = ledger();
+        ledger.committed = AMOUNT;
+
+        vault.release_grant(Some(&mut ledger), mint, 400).unwrap();
+        assert_eq!(ledger.committed, 600);
+        assert_eq!(vault.committed, AMOUNT);
+
+        // Nothing reserved, nothing to release - the ledger can be left out
+        vault.release_grant(None, mint, 0).unwrap();
+        assert_eq!(
+            vault.release_grant(None, mint, 600).unwrap_err(),
+            ErrorCode::TokenLedgerRequired.into()
+        );
+
+        vault.release_grant(None, None, 400).unwrap();
+        assert_eq!(vault.committed, 600);
+    }
+
+    #[test]
+    fn any_of_several_roles_is_enough() {
+        let admins = vec![Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+        let config = Config {
+            fund: Pubkey::new_unique(),
+            admins: admins.clone(),
+            roles: vec![Config::ROLE_TREASURER, Config::ROLE_PAUSER, Config::ROLE_REVIEWER],
+            admin_threshold: 2,
+            admin_change_count: 0,
+            governance_mint: Pubkey::new_unique(),
+            params: GovernanceParams::DEFAULT,
+            emergency: EmergencyPolicy::DEFAULT,
+            paused: false,
+            bump: 0,
+        };
+        let roles = Config::ROLE_TREASURER | Config::ROLE_PAUSER;
+
+        config.require_any_role(&admins[0], roles).unwrap();
+        config.require_any_role(&admins[1], roles).unwrap();
+        assert_eq!(
+            config.require_any_role(&admins[2], roles).unwrap_err(),
+            ErrorCode::MissingRole.into()
+        );
+        assert_eq!(
+            config.require_any_role(&Pubkey::new_unique(), roles).unwrap_err(),
+            ErrorCode::Unauthorized.into()
+        );
+    }
+
+    #[test]
+    fn late_depositors_cannot_capture_earlier_donations() {
+        let mut vault = Vault {
+            total_deposited: AMOUNT,
+            total_claimed: 0,
+            committed: 0,
+            total_burned: 0,
+            total_withdrawn: 0,
+            external_donations: 500,
+            emergency_withdrawn: 0,
+            total_shares: AMOUNT,
+            bump: 0,
+        };
+        // The first depositor's 1000 plus a 500 donation
+        let available = AMOUNT + 500;
+
+        let shares = vault.shares_for_deposit(AMOUNT, available);
+        assert_eq!(shares, 666);
+        vault.total_shares += shares;
+
+        // Leaving straight away returns no more than was put in
+        let payout = vault.payout_for_shares(shares, available + AMOUNT);
+        assert!(payout <= AMOUNT);
+        assert_eq!(payout, 999);
+
+        // The donation stays with the first depositor
+        let first = vault.payout_for_shares(AMOUNT, available + AMOUNT);
+        assert_eq!(first, 1_500);
+    }
+
+    #[test]
+    fn deposits_into_an_underwater_vault_do_not_dilute_holders() {
+        let vault = Vault {
+            total_deposited: AMOUNT,
+            total_claimed: 0,
+            committed: 0,
+            total_burned: 0,
+            total_withdrawn: 0,
+            external_donations: 0,
+            emergency_withdrawn: 0,
+            total_shares: AMOUNT,
+            bump: 0,
+        };
+        // Half the vault is gone, so each share is worth half a lamport
+        let shares = vault.shares_for_deposit(AMOUNT, AMOUNT / 2);
+        assert_eq!(shares, 2 * AMOUNT);
+    }
+
+    #[test]
+    fn params_can_only_be_tightened_past_the_defaults() {
+        let defaults = GovernanceParams::DEFAULT;
+        defaults.validate().unwrap();
+
+        let stricter = GovernanceParams {
+            min_quorum: 10_000,
+            min_quadratic_quorum: 50,
+            approval_threshold_bps: 6_600,
+            multisig_threshold: 1_000,
+            ..defaults
+        };
+        stricter.validate().unwrap();
+
+        let looser = [
+            GovernanceParams { min_quorum: 1, ..defaults },
+            GovernanceParams { min_quadratic_quorum: 1, ..defaults },
+            GovernanceParams { approval_threshold_bps: 4_999, ..defaults },
+            GovernanceParams { multisig_threshold: u64::MAX, ..defaults },
+        ];
+        for params in looser {
+            assert_eq!(
+                params.validate().unwrap_err(),
+                ErrorCode::InvalidGovernanceParams.into()
+            );
+        }
+    }
+
+    #[test]
+    fn quadratic_proposals_use_their_own_quorum() {
+        let params = GovernanceParams {
+            min_quorum: 1_000_000,
+            min_quadratic_quorum: 20,
+            ..GovernanceParams::DEFAULT
+        };
+        assert_eq!(params.quorum_for(VotingMode::TokenWeighted), 1_000_000);
+        assert_eq!(params.quorum_for(VotingMode::Quadratic), 20);
+    }
+}
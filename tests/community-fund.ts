@@ -1,13 +1,52 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { CommunityFund } from "../target/types/community_fund";
-import { Keypair, PublicKey } from "@solana/web3.js";
+import { Keypair, PublicKey, SystemProgram, Transaction } from "@solana/web3.js";
 import { expect } from "chai";
 import { BN } from "bn.js";
+import {
+  ExtensionType,
+  TOKEN_2022_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  createAssociatedTokenAccount,
+  createInitializeMintInstruction,
+  createInitializeTransferFeeConfigInstruction,
+  createMint,
+  getMintLen,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+
+// Fund realm the suite runs in - every other PDA is derived under it
+const FUND_ID = 1;
+
+// Role bits, matching Config::ROLE_*
+const ROLE_REVIEWER = 1;
+const ROLE_TREASURER = 2;
+const ROLE_PAUSER = 4;
+const ROLE_PARAMS = 8;
+const ALL_ROLES = ROLE_REVIEWER | ROLE_TREASURER | ROLE_PAUSER | ROLE_PARAMS;
+
+const u64Buffer = (value: number) => {
+  const buffer = Buffer.alloc(8);
+  buffer.writeBigUInt64BE(BigInt(value));
+  return buffer;
+};
+
+const generateFundPDA = (fundId: number, programId: PublicKey) => {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("fund"), u64Buffer(fundId)],
+    programId
+  )[0];
+};
 
-const generatePDA = (key: PublicKey, programId: PublicKey) => {
+const generatePDA = (
+  key: PublicKey,
+  programId: PublicKey,
+  fund: PublicKey = generateFundPDA(FUND_ID, programId)
+) => {
   return PublicKey.findProgramAddressSync(
-    [Buffer.from("user_profile"), key.toBuffer()],
+    [Buffer.from("user_profile"), fund.toBuffer(), key.toBuffer()],
     programId
   )[0];
 };
@@ -15,19 +54,21 @@ const generatePDA = (key: PublicKey, programId: PublicKey) => {
 const generateProposalPDA = (
   user: PublicKey,
   programId: PublicKey,
-  count: number
+  count: number,
+  fund: PublicKey = generateFundPDA(FUND_ID, programId)
 ) => {
-  const countBuffer = Buffer.alloc(8);
-  countBuffer.writeBigUInt64BE(BigInt(count));
   return PublicKey.findProgramAddressSync(
-    [Buffer.from("proposal"), user.toBuffer(), countBuffer],
+    [Buffer.from("proposal"), fund.toBuffer(), user.toBuffer(), u64Buffer(count)],
     programId
   )[0];
 };
 
-const generateConfigPDA = (programId: PublicKey) => {
+const generateConfigPDA = (
+  programId: PublicKey,
+  fund: PublicKey = generateFundPDA(FUND_ID, programId)
+) => {
   return PublicKey.findProgramAddressSync(
-    [Buffer.from("config")],
+    [Buffer.from("config"), fund.toBuffer()],
     programId
   )[0];
 };
@@ -43,23 +84,119 @@ const generateProgramDataPDA = (programId: PublicKey) => {
   )[0];
 };
 
+// Votes and receipts are keyed by the proposal account
 const generateVotePDA = (
   voter: PublicKey,
   owner: PublicKey,
   programId: PublicKey,
-  proposalId: number
+  proposalId: number,
+  fund: PublicKey = generateFundPDA(FUND_ID, programId)
+) => {
+  const proposal = generateProposalPDA(owner, programId, proposalId, fund);
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("vote"), proposal.toBuffer(), voter.toBuffer()],
+    programId
+  )[0];
+};
+
+const generateVoterLockPDA = (
+  voter: PublicKey,
+  programId: PublicKey,
+  fund: PublicKey = generateFundPDA(FUND_ID, programId)
+) => {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("voter_lock"), fund.toBuffer(), voter.toBuffer()],
+    programId
+  )[0];
+};
+
+const generateLockEscrowPDA = (
+  voter: PublicKey,
+  programId: PublicKey,
+  fund: PublicKey = generateFundPDA(FUND_ID, programId)
+) => {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("lock_escrow"), fund.toBuffer(), voter.toBuffer()],
+    programId
+  )[0];
+};
+
+const generateDelegationPDA = (
+  delegator: PublicKey,
+  category: number,
+  programId: PublicKey,
+  fund: PublicKey = generateFundPDA(FUND_ID, programId)
+) => {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("delegation"), fund.toBuffer(), delegator.toBuffer(), Buffer.from([category])],
+    programId
+  )[0];
+};
+
+const generateDelegationReceiptPDA = (
+  delegator: PublicKey,
+  owner: PublicKey,
+  programId: PublicKey,
+  proposalId: number,
+  fund: PublicKey = generateFundPDA(FUND_ID, programId)
+) => {
+  const proposal = generateProposalPDA(owner, programId, proposalId, fund);
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("delegation_receipt"), proposal.toBuffer(), delegator.toBuffer()],
+    programId
+  )[0];
+};
+
+const generateVoiceCreditsPDA = (
+  voter: PublicKey,
+  programId: PublicKey,
+  fund: PublicKey = generateFundPDA(FUND_ID, programId)
+) => {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("voice_credits"), fund.toBuffer(), voter.toBuffer()],
+    programId
+  )[0];
+};
+
+const generateTokenLedgerPDA = (vault: PublicKey, mint: PublicKey, programId: PublicKey) => {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("token_ledger"), vault.toBuffer(), mint.toBuffer()],
+    programId
+  )[0];
+};
+
+const generateVaultTokenPDA = (vault: PublicKey, mint: PublicKey, programId: PublicKey) => {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("vault_token"), vault.toBuffer(), mint.toBuffer()],
+    programId
+  )[0];
+};
+
+const generateDepositorRecordPDA = (vault: PublicKey, depositor: PublicKey, programId: PublicKey) => {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("depositor"), vault.toBuffer(), depositor.toBuffer()],
+    programId
+  )[0];
+};
+
+const generateRoundPDA = (
+  roundId: number,
+  programId: PublicKey,
+  fund: PublicKey = generateFundPDA(FUND_ID, programId)
 ) => {
-  const proposalIdBuffer = Buffer.alloc(8);
-  proposalIdBuffer.writeBigUInt64BE(BigInt(proposalId));
   return PublicKey.findProgramAddressSync(
-    [Buffer.from("vote"), voter.toBuffer(), owner.toBuffer(), proposalIdBuffer],
+    [Buffer.from("round"), fund.toBuffer(), u64Buffer(roundId)],
     programId
   )[0];
 };
 
-const generateVaultPDA = (programId: PublicKey) => {
+const generateVaultPDA = (
+  roundId: number,
+  programId: PublicKey,
+  fund: PublicKey = generateFundPDA(FUND_ID, programId)
+) => {
   return PublicKey.findProgramAddressSync(
-    [Buffer.from("vault")],
+    [Buffer.from("vault"), fund.toBuffer(), u64Buffer(roundId)],
     programId
   )[0];
 };
@@ -71,6 +208,7 @@ describe("community-fund", () => {
   const user = provider.wallet.publicKey;
 
   const program = anchor.workspace.communityFund as Program<CommunityFund>;
+  const fundPDA = generateFundPDA(FUND_ID, program.programId);
   const userProfilePDA = generatePDA(user, program.programId);
 
   // Additional test users
@@ -83,10 +221,227 @@ describe("community-fund", () => {
 
   let isAdminInitialized = false;
 
+  // Governance token - voters lock it in escrow to get vote weight
+  let governanceMint: PublicKey;
+  let userTokenAccount: PublicKey;
+  let bobTokenAccount: PublicKey;
+  let aliceTokenAccount: PublicKey;
+  const USER_TOKENS = 40_000_000;
+  const BOB_TOKENS = 30_000_000;
+  const ALICE_TOKENS = 35_000_000;
+
+  // Lock bounds from VoterLock
+  const MIN_LOCK_DURATION = 604800;
+  const MAX_LOCK_DURATION = 4 * 365 * 86400;
+
+  // Proposal categories - delegations can be scoped to one of them
+  const GENERAL_CATEGORY = 0;
+  const ALL_CATEGORIES = 255;
+
+  // Funding rounds - every proposal applies to one; the token round pays out in SPL tokens
+  const ROUND_ID = 1;
+  const TOKEN_ROUND_ID = 2;
+  const QUADRATIC_ROUND_ID = 5;
+  const ROUND_BUDGET = 5_000 * anchor.web3.LAMPORTS_PER_SOL;
+  const roundPDA = generateRoundPDA(ROUND_ID, program.programId);
+  const tokenRoundPDA = generateRoundPDA(TOKEN_ROUND_ID, program.programId);
+  const quadraticRoundPDA = generateRoundPDA(QUADRATIC_ROUND_ID, program.programId);
+  const roundVaultPDA = generateVaultPDA(ROUND_ID, program.programId);
+
+  // Voting modes
+  const TOKEN_WEIGHTED = { tokenWeighted: {} } as any;
+  const QUADRATIC = { quadratic: {} } as any;
+
+  // Opens a round that accepts applications for the next 30 days
+  const createRound = async (roundId: number, mint: PublicKey | null, votingMode = TOKEN_WEIGHTED) => {
+    const now = Math.floor(Date.now() / 1000);
+    const applicationEnd = now + 30 * 86400;
+    await program.methods
+      .createRound(
+        new BN(roundId),
+        mint,
+        votingMode,
+        new BN(ROUND_BUDGET),
+        new BN(now - 60),
+        new BN(applicationEnd),
+        new BN(applicationEnd + 120 * 86400)
+      )
+      .accounts({ admin: user, fund: fundPDA })
+      .rpc();
+  };
+
+  // Casts a vote from `voter` (the provider wallet when omitted) and returns the vote PDA.
+  // `delegators` adds the weight each of them delegated to `voter`;
+  // `quadraticVotes` is the number of votes to buy on quadratic proposals.
+  const castVote = async (
+    owner: PublicKey,
+    proposalId: number,
+    voter?: Keypair,
+    choice: object = { yes: {} },
+    delegators: { key: PublicKey; category: number }[] = [],
+    quadraticVotes: BN | null = null
+  ) => {
+    const voterKey = voter ? voter.publicKey : user;
+    const votePDA = generateVotePDA(voterKey, owner, program.programId, proposalId);
+    const lockPDA = generateVoterLockPDA(voterKey, program.programId);
+    const hasLock = (await provider.connection.getAccountInfo(lockPDA)) !== null;
+    const creditsPDA = generateVoiceCreditsPDA(voterKey, program.programId);
+    const hasCredits = (await provider.connection.getAccountInfo(creditsPDA)) !== null;
+
+    const remainingAccounts = delegators.flatMap((delegator) => [
+      { pubkey: generateDelegationPDA(delegator.key, delegator.category, program.programId), isSigner: false, isWritable: false },
+      { pubkey: generateVoterLockPDA(delegator.key, program.programId), isSigner: false, isWritable: false },
+      { pubkey: generateVotePDA(delegator.key, owner, program.programId, proposalId), isSigner: false, isWritable: false },
+      { pubkey: generateDelegationReceiptPDA(delegator.key, owner, program.programId, proposalId), isSigner: false, isWritable: true },
+    ]);
+
+    await program.methods
+      .voteOnProposal(new BN(proposalId), owner, choice as any, quadraticVotes)
+      .accounts({
+        voteAccount: votePDA,
+        user: voterKey,
+        proposal: generateProposalPDA(owner, program.programId, proposalId),
+        voterLock: hasLock ? lockPDA : null,
+        voiceCredits: hasCredits ? creditsPDA : null,
+        fund: fundPDA,
+      })
+      .remainingAccounts(remainingAccounts)
+      .signers(voter ? [voter] : [])
+      .rpc();
+    return votePDA;
+  };
+
+  // Funds a fresh keypair with SOL and locked governance tokens
+  const createLockedVoter = async (amount: number, lockDuration: number) => {
+    const payer = (provider.wallet as anchor.Wallet).payer;
+    const voter = Keypair.generate();
+    const airdropSig = await provider.connection.requestAirdrop(
+      voter.publicKey,
+      anchor.web3.LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(airdropSig, "confirmed");
+
+    const tokenAccount = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      payer,
+      governanceMint,
+      voter.publicKey
+    );
+    await mintTo(provider.connection, payer, governanceMint, tokenAccount.address, user, amount);
+
+    await program.methods
+      .lockTokens(new BN(amount), new BN(lockDuration))
+      .accounts({
+        user: voter.publicKey,
+        userTokenAccount: tokenAccount.address,
+        governanceMint: governanceMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        fund: fundPDA,
+      })
+      .signers([voter])
+      .rpc();
+    return voter;
+  };
+
+  // ==================== GOVERNANCE TOKEN SETUP ====================
+
+  it("Create governance mint and fund voters", async () => {
+    const payer = (provider.wallet as anchor.Wallet).payer;
+    governanceMint = await createMint(provider.connection, payer, user, null, 6);
+
+    const holders = [
+      { owner: user, amount: USER_TOKENS },
+      { owner: bob.publicKey, amount: BOB_TOKENS },
+      { owner: alice.publicKey, amount: ALICE_TOKENS },
+    ];
+    const tokenAccounts: PublicKey[] = [];
+    for (const holder of holders) {
+      const tokenAccount = await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        payer,
+        governanceMint,
+        holder.owner
+      );
+      await mintTo(provider.connection, payer, governanceMint, tokenAccount.address, user, holder.amount);
+      tokenAccounts.push(tokenAccount.address);
+    }
+    [userTokenAccount, bobTokenAccount, aliceTokenAccount] = tokenAccounts;
+
+    console.log(`✅ Governance mint created: ${governanceMint.toString()}`);
+  });
+
+  // ==================== ADMIN INITIALIZATION TESTS ====================
+
+  // The suite runs against a `local-testing` build, which skips the upgrade authority
+  // check - the check itself is covered by the unit tests in admin/initialize.rs
+  it("Initialize admin", async () => {
+    const configPDA = generateConfigPDA(program.programId);
+    const programDataPDA = generateProgramDataPDA(program.programId);
+
+    await program.methods
+      .initializeAdmin(
+        new BN(FUND_ID),
+        [user, admin2.publicKey, admin3.publicKey],
+        [ALL_ROLES, ALL_ROLES, ALL_ROLES],
+        2
+      )
+      .accounts({
+        user: user,
+        programData: programDataPDA,
+        governanceMint: governanceMint,
+        fund: fundPDA,
+      })
+      .rpc();
+
+    const config = await program.account.config.fetch(configPDA);
+    expect(config.admins[0].toString()).to.equal(user.toString());
+    expect(config.admins[1].toString()).to.equal(admin2.publicKey.toString());
+    expect(config.admins[2].toString()).to.equal(admin3.publicKey.toString());
+    expect(config.governanceMint.toString()).to.equal(governanceMint.toString());
+    console.log("✅ Admin initialized with 3 admins:");
+    console.log(`   Admin 1: ${config.admins[0].toString()}`);
+    console.log(`   Admin 2: ${config.admins[1].toString()}`);
+    console.log(`   Admin 3: ${config.admins[2].toString()}`);
+    isAdminInitialized = true;
+  });
+
+  it("Cannot double initialize admin", async () => {
+    if (!isAdminInitialized) {
+      console.log("⚠️  Skipping: Admin not initialized");
+      return;
+    }
+
+    const programDataPDA = generateProgramDataPDA(program.programId);
+
+    try {
+      await program.methods
+        .initializeAdmin(
+          new BN(FUND_ID),
+          [user, admin2.publicKey, admin3.publicKey],
+          [ALL_ROLES, ALL_ROLES, ALL_ROLES],
+          2
+        )
+        .accounts({
+          user: user,
+          programData: programDataPDA,
+          governanceMint: governanceMint,
+          fund: fundPDA,
+        })
+        .rpc();
+      expect.fail("Expected transaction to fail but it succeeded");
+    } catch (error) {
+      expect(error.message).to.include("already in use");
+      console.log("✅ Correctly prevented double admin initialization");
+    }
+  });
+
   // ==================== USER PROFILE TESTS ====================
 
   it("Initialize user", async () => {
-    const tx = await program.methods.initializeUser().rpc();
+    const tx = await program.methods
+      .initializeUser()
+      .accounts({ fund: fundPDA })
+      .rpc();
 
     const userProfile = await program.account.userProfile.fetch(userProfilePDA);
     expect(userProfile.proposalCount.toNumber()).to.equal(0);
@@ -95,7 +450,10 @@ describe("community-fund", () => {
 
   it("Cannot double initialize user", async () => {
     try {
-      await program.methods.initializeUser().rpc();
+      await program.methods
+        .initializeUser()
+        .accounts({ fund: fundPDA })
+        .rpc();
       expect.fail("Expected transaction to fail but it succeeded");
     } catch (error) {
       expect(error.message).to.include("already in use");
@@ -103,6 +461,79 @@ describe("community-fund", () => {
     }
   });
 
+  // ==================== FUNDING ROUND TESTS ====================
+
+  it("Admin creates a funding round with its own vault", async () => {
+    await createRound(ROUND_ID, null);
+
+    const round = await program.account.round.fetch(roundPDA);
+    expect(round.id.toNumber()).to.equal(ROUND_ID);
+    expect(round.mint).to.be.null;
+    expect(round.budget.toNumber()).to.equal(ROUND_BUDGET);
+    expect(round.allocated.toNumber()).to.equal(0);
+    // Voting runs one voting period past the application window
+    expect(round.votingEnd.toNumber()).to.equal(round.applicationEnd.toNumber() + 604800);
+
+    const vault = await program.account.vault.fetch(generateVaultPDA(ROUND_ID, program.programId));
+    expect(vault.totalDeposited.toNumber()).to.equal(0);
+    expect(vault.totalClaimed.toNumber()).to.equal(0);
+    console.log("✅ Round 1 created with a 5000 SOL budget and an empty vault");
+  });
+
+  it("Cannot create the same round twice", async () => {
+    try {
+      await createRound(ROUND_ID, null);
+      expect.fail("Expected transaction to fail but it succeeded");
+    } catch (error) {
+      expect(error.message).to.include("already in use");
+      console.log("✅ Correctly prevented duplicate round");
+    }
+  });
+
+  it("Non-admin cannot create a round", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    try {
+      await program.methods
+        .createRound(new BN(99), null, TOKEN_WEIGHTED, new BN(ROUND_BUDGET), new BN(now), new BN(now + 86400), new BN(now + 30 * 86400))
+        .accounts({ admin: bob.publicKey, fund: fundPDA })
+        .signers([bob])
+        .rpc();
+      expect.fail("Expected transaction to fail but it succeeded");
+    } catch (error) {
+      expect(error.toString()).to.include("Unauthorized");
+      console.log("✅ Non-admin correctly prevented from creating a round");
+    }
+  });
+
+  it("Proposals are only accepted during the application window", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const futureRoundId = 3;
+    const futureRoundPDA = generateRoundPDA(futureRoundId, program.programId);
+    await program.methods
+      .createRound(
+        new BN(futureRoundId),
+        null,
+        TOKEN_WEIGHTED,
+        new BN(ROUND_BUDGET),
+        new BN(now + 86400),
+        new BN(now + 2 * 86400),
+        new BN(now + 30 * 86400)
+      )
+      .accounts({ admin: user, fund: fundPDA })
+      .rpc();
+
+    try {
+      await program.methods
+        .createProposal("Too Early", "Round not open yet", new BN(100000000), GENERAL_CATEGORY, [], null)
+        .accounts({ round: futureRoundPDA, fund: fundPDA })
+        .rpc();
+      expect.fail("Expected transaction to fail but it succeeded");
+    } catch (error) {
+      expect(error.toString()).to.include("RoundNotAccepting");
+      console.log("✅ Application before the window opens correctly rejected");
+    }
+  });
+
   // ==================== PROPOSAL CREATION TESTS ====================
 
   it("Create proposal", async () => {
@@ -115,12 +546,17 @@ describe("community-fund", () => {
       .createProposal(
         "Test Proposal",
         "Test Description",
-        new anchor.BN(1000000000)
+        new anchor.BN(1000000000),
+        GENERAL_CATEGORY,
+        [],
+        null
       )
       .accounts({
         proposal: proposalPDA,
         userProfile: userProfilePDA,
         user: user,
+        round: roundPDA,
+        fund: fundPDA,
       })
       .rpc();
 
@@ -128,7 +564,9 @@ describe("community-fund", () => {
     expect(proposal.title).to.equal("Test Proposal");
     expect(proposal.description).to.equal("Test Description");
     expect(proposal.amountRequested.toNumber()).to.equal(1000000000);
-    expect(proposal.voteCount.toNumber()).to.equal(0);
+    expect(proposal.yesVotes.toNumber()).to.equal(0);
+    expect(proposal.noVotes.toNumber()).to.equal(0);
+    expect(proposal.abstainVotes.toNumber()).to.equal(0);
     expect(proposal.status).to.deep.equal({ pending: {} });
     expect(proposal.fundingApprovals.length).to.equal(0);
 
@@ -151,12 +589,17 @@ describe("community-fund", () => {
         .createProposal(
           `Proposal ${i + 2}`,
           `Description ${i + 2}`,
-          new anchor.BN(500000000 * (i + 1))
+          new anchor.BN(500000000 * (i + 1)),
+          GENERAL_CATEGORY,
+          [],
+          null
         )
         .accounts({
           proposal: proposalPDA,
           userProfile: userProfilePDA,
           user: user,
+          round: roundPDA,
+          fund: fundPDA,
         })
         .rpc();
     }
@@ -180,11 +623,13 @@ describe("community-fund", () => {
     const maxDescription = "B".repeat(200);
 
     await program.methods
-      .createProposal(maxTitle, maxDescription, new anchor.BN(1000000000))
+      .createProposal(maxTitle, maxDescription, new anchor.BN(1000000000), GENERAL_CATEGORY, [], null)
       .accounts({
         proposal: proposalPDA,
         userProfile: userProfilePDA,
         user: user,
+        round: roundPDA,
+        fund: fundPDA,
       })
       .rpc();
 
@@ -204,11 +649,13 @@ describe("community-fund", () => {
     const largeAmount = new anchor.BN(2_000_000_000_000);
 
     await program.methods
-      .createProposal("Large Amount Proposal", "Need 2-of-3 approval", largeAmount)
+      .createProposal("Large Amount Proposal", "Need 2-of-3 approval", largeAmount, GENERAL_CATEGORY, [], null)
       .accounts({
         proposal: proposalPDA,
         userProfile: userProfilePDA,
         user: user,
+        round: roundPDA,
+        fund: fundPDA,
       })
       .rpc();
 
@@ -223,6 +670,7 @@ describe("community-fund", () => {
     const proposalPDA = generateProposalPDA(user, program.programId, 0);
     const tx = await program.methods
       .updateProposal(new BN(0), "Updated Title", "Updated Description")
+      .accounts({ fund: fundPDA })
       .rpc();
 
     const proposal = await program.account.proposal.fetch(proposalPDA);
@@ -240,6 +688,7 @@ describe("community-fund", () => {
         .accounts({
           owner: bob.publicKey,
           proposal: proposalPDA,
+          fund: fundPDA,
         })
         .signers([bob])
         .rpc();
@@ -255,75 +704,97 @@ describe("community-fund", () => {
     expect(proposal.description).to.equal("Updated Description");
   });
 
-  // ==================== ADMIN INITIALIZATION TESTS ====================
-
-  it("Initialize admin with upgrade authority verification", async () => {
-    const configPDA = generateConfigPDA(program.programId);
-    const programDataPDA = generateProgramDataPDA(program.programId);
+  // ==================== GOVERNANCE PARAMS TESTS ====================
 
-    try {
-      const tx = await program.methods
-        .initializeAdmin(admin2.publicKey, admin3.publicKey)
-        .accounts({
-          user: user,
-          programData: programDataPDA,
-        })
-        .rpc();
+  const DEFAULT_PARAMS = {
+    votingPeriod: new BN(604800),
+    minQuorum: new BN(100),
+    minQuadraticQuorum: new BN(10),
+    approvalThresholdBps: 5000,
+    multisigThreshold: new BN(1_000_000_000_000),
+  };
 
-      const config = await program.account.config.fetch(configPDA);
-      expect(config.admins[0].toString()).to.equal(user.toString());
-      expect(config.admins[1].toString()).to.equal(admin2.publicKey.toString());
-      expect(config.admins[2].toString()).to.equal(admin3.publicKey.toString());
-      console.log("✅ Admin initialized with 3 admins:");
-      console.log(`   Admin 1: ${config.admins[0].toString()}`);
-      console.log(`   Admin 2: ${config.admins[1].toString()}`);
-      console.log(`   Admin 3: ${config.admins[2].toString()}`);
-      isAdminInitialized = true;
-    } catch (error) {
-      console.log("⚠️  Security check prevented initialization in local test");
-      console.log("⚠️  Admin initialization commented out for local testing");
-      // For local testing with commented upgrade check, try again
-      const tx = await program.methods
-        .initializeAdmin(admin2.publicKey, admin3.publicKey)
-        .accounts({
-          user: user,
-          programData: programDataPDA,
-        })
-        .rpc();
+  it("Config starts with default governance params", async () => {
+    const config = await program.account.config.fetch(generateConfigPDA(program.programId));
+    expect(config.params.votingPeriod.toNumber()).to.equal(604800);
+    expect(config.params.minQuorum.toNumber()).to.equal(100);
+    expect(config.params.minQuadraticQuorum.toNumber()).to.equal(10);
+    expect(config.params.approvalThresholdBps).to.equal(5000);
+    expect(config.params.multisigThreshold.toString()).to.equal("1000000000000");
 
-      const config = await program.account.config.fetch(configPDA);
-      expect(config.admins[0].toString()).to.equal(user.toString());
-      expect(config.admins[1].toString()).to.equal(admin2.publicKey.toString());
-      expect(config.admins[2].toString()).to.equal(admin3.publicKey.toString());
-      console.log("✅ Admin initialized with 3 admins:");
-      console.log(`   Admin 1: ${config.admins[0].toString()}`);
-      console.log(`   Admin 2: ${config.admins[1].toString()}`);
-      console.log(`   Admin 3: ${config.admins[2].toString()}`);
-      isAdminInitialized = true;
-    }
+    const proposalPDA = generateProposalPDA(user, program.programId, 0);
+    const proposal = await program.account.proposal.fetch(proposalPDA);
+    expect(proposal.params.votingPeriod.toNumber()).to.equal(604800);
+    console.log("✅ Default params stored in config and copied into proposals");
   });
 
-  it("Cannot double initialize admin", async () => {
-    if (!isAdminInitialized) {
-      console.log("⚠️  Skipping: Admin not initialized");
-      return;
-    }
-
-    const programDataPDA = generateProgramDataPDA(program.programId);
-
+  it("Non-admin cannot update governance params", async () => {
     try {
       await program.methods
-        .initializeAdmin(admin2.publicKey, admin3.publicKey)
-        .accounts({
-          user: user,
-          programData: programDataPDA,
-        })
+        .updateParams({ ...DEFAULT_PARAMS, minQuorum: new BN(1) })
+        .accounts({ admin: bob.publicKey, fund: fundPDA })
+        .signers([bob])
         .rpc();
       expect.fail("Expected transaction to fail but it succeeded");
     } catch (error) {
-      expect(error.message).to.include("already in use");
-      console.log("✅ Correctly prevented double admin initialization");
+      expect(error.message).to.include("Unauthorized");
+      console.log("✅ Non-admin correctly prevented from updating params");
+    }
+  });
+
+  it("Invalid governance params are rejected", async () => {
+    const invalid = [
+      // Below the minimum even in local-testing builds
+      { ...DEFAULT_PARAMS, votingPeriod: new BN(1) },
+      { ...DEFAULT_PARAMS, minQuorum: new BN(0) },
+      { ...DEFAULT_PARAMS, approvalThresholdBps: 10_001 },
+      { ...DEFAULT_PARAMS, multisigThreshold: new BN(0) },
+      // A lone params admin can't loosen approvals past the defaults
+      { ...DEFAULT_PARAMS, minQuorum: new BN(1) },
+      { ...DEFAULT_PARAMS, minQuadraticQuorum: new BN(1) },
+      { ...DEFAULT_PARAMS, approvalThresholdBps: 4_999 },
+      { ...DEFAULT_PARAMS, multisigThreshold: new BN("18446744073709551615") },
+    ];
+    for (const params of invalid) {
+      try {
+        await program.methods.updateParams(params).accounts({ admin: user, fund: fundPDA }).rpc();
+        expect.fail("Expected transaction to fail but it succeeded");
+      } catch (error) {
+        expect(error.message).to.include("InvalidGovernanceParams");
+      }
     }
+    console.log("✅ Out-of-range params correctly rejected");
+  });
+
+  it("Updated params apply to new proposals only", async () => {
+    const configPDA = generateConfigPDA(program.programId);
+    await program.methods
+      .updateParams({ ...DEFAULT_PARAMS, votingPeriod: new BN(2 * 86400) })
+      .accounts({ admin: user, fund: fundPDA })
+      .rpc();
+
+    const count = await program.account.userProfile
+      .fetch(userProfilePDA)
+      .then((userProfile) => userProfile.proposalCount.toNumber());
+    await program.methods
+      .createProposal("Short Window", "Created under a two-day voting period", new BN(100000000), GENERAL_CATEGORY, [], null)
+      .accounts({ round: roundPDA, fund: fundPDA })
+      .rpc();
+
+    const fresh = await program.account.proposal.fetch(generateProposalPDA(user, program.programId, count));
+    expect(fresh.params.votingPeriod.toNumber()).to.equal(2 * 86400);
+    const open = await program.account.proposal.fetch(generateProposalPDA(user, program.programId, 0));
+    expect(open.params.votingPeriod.toNumber()).to.equal(604800);
+
+    // The round owns the voting window - the new period only applies to rounds created later
+    const round = await program.account.round.fetch(roundPDA);
+    expect(fresh.votingEnd.toNumber()).to.equal(round.votingEnd.toNumber());
+
+    // Restore defaults for the remaining tests
+    await program.methods.updateParams(DEFAULT_PARAMS).accounts({ admin: user, fund: fundPDA }).rpc();
+    const config = await program.account.config.fetch(configPDA);
+    expect(config.params.votingPeriod.toNumber()).to.equal(604800);
+    console.log("✅ Open proposals keep the params they were created with");
   });
 
   // ==================== PROPOSAL REJECTION TESTS ====================
@@ -344,6 +815,8 @@ describe("community-fund", () => {
         admin: user,
         proposal: proposalPDA,
         config: configPDA,
+        round: roundPDA,
+        fund: fundPDA,
       })
       .rpc();
 
@@ -368,6 +841,8 @@ describe("community-fund", () => {
         admin: admin2.publicKey,
         proposal: proposalPDA,
         config: configPDA,
+        round: roundPDA,
+        fund: fundPDA,
       })
       .signers([admin2])
       .rpc();
@@ -393,6 +868,8 @@ describe("community-fund", () => {
         admin: admin3.publicKey,
         proposal: proposalPDA,
         config: configPDA,
+        round: roundPDA,
+        fund: fundPDA,
       })
       .signers([admin3])
       .rpc();
@@ -419,6 +896,8 @@ describe("community-fund", () => {
           admin: bob.publicKey,
           proposal: proposalPDA,
           config: configPDA,
+          round: roundPDA,
+          fund: fundPDA,
         })
         .signers([bob])
         .rpc();
@@ -430,130 +909,241 @@ describe("community-fund", () => {
     }
   });
 
-  // ==================== APPROVE FUNDING TESTS ====================
-
-  it("Single admin can approve small amount proposal", async () => {
-    if (!isAdminInitialized) {
-      console.log("⚠️  Skipping: Admin not initialized");
-      return;
-    }
-
-    // Proposal 0 has amount 1000000000 (1 SOL) - below threshold
-    const proposalPDA = generateProposalPDA(user, program.programId, 0);
-    const configPDA = generateConfigPDA(program.programId);
+  it("Admin of another fund cannot reject this fund's proposal", async () => {
+    // A second community shares the deployment with its own admin set
+    const otherFundId = 2;
+    const otherFundPDA = generateFundPDA(otherFundId, program.programId);
+    const otherAdmin = Keypair.generate();
+    const airdropSig = await provider.connection.requestAirdrop(
+      otherAdmin.publicKey,
+      anchor.web3.LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(airdropSig, "confirmed");
 
     await program.methods
-      .approveFunding(new BN(0), user)
+      .initializeAdmin(
+        new BN(otherFundId),
+        [otherAdmin.publicKey, Keypair.generate().publicKey, Keypair.generate().publicKey],
+        [ALL_ROLES, ALL_ROLES, ALL_ROLES],
+        2
+      )
       .accounts({
-        admin: user,
-        proposal: proposalPDA,
-        config: configPDA,
+        user: otherAdmin.publicKey,
+        programData: generateProgramDataPDA(program.programId),
+        governanceMint: governanceMint,
+        fund: otherFundPDA,
       })
+      .signers([otherAdmin])
       .rpc();
 
-    const proposal = await program.account.proposal.fetch(proposalPDA);
-    expect(proposal.status).to.deep.equal({ approved: {} });
-    console.log("✅ Small amount approved by single admin");
-  });
-
-  it("Large amount requires 2-of-3 multisig - First approval", async () => {
-    if (!isAdminInitialized) {
-      console.log("⚠️  Skipping: Admin not initialized");
-      return;
-    }
-
-    // Proposal 5 has 2000 SOL (2_000_000_000_000) - above threshold
-    const proposalPDA = generateProposalPDA(user, program.programId, 5);
-    const configPDA = generateConfigPDA(program.programId);
+    const otherFund = await program.account.fund.fetch(otherFundPDA);
+    expect(otherFund.id.toNumber()).to.equal(otherFundId);
+    expect(otherFund.creator.toString()).to.equal(otherAdmin.publicKey.toString());
 
+    // Proposal 4 belongs to fund 1, so it can't be addressed through fund 2
+    try {
+      await program.methods
+        .rejectProposal(new BN(4), user)
+        .accounts({
+          admin: otherAdmin.publicKey,
+          proposal: generateProposalPDA(user, program.programId, 4),
+          config: generateConfigPDA(program.programId, otherFundPDA),
+          round: generateRoundPDA(ROUND_ID, program.programId, otherFundPDA),
+          fund: otherFundPDA,
+        })
+        .signers([otherAdmin])
+        .rpc();
+      expect.fail("Expected transaction to fail but it succeeded");
+    } catch (error) {
+      expect(error.toString()).to.match(/ConstraintSeeds|AccountNotInitialized/);
+    }
+
+    // Nor can fund 2's admin act through fund 1's config
+    try {
+      await program.methods
+        .rejectProposal(new BN(4), user)
+        .accounts({
+          admin: otherAdmin.publicKey,
+          proposal: generateProposalPDA(user, program.programId, 4),
+          config: generateConfigPDA(program.programId),
+          round: roundPDA,
+          fund: fundPDA,
+        })
+        .signers([otherAdmin])
+        .rpc();
+      expect.fail("Expected transaction to fail but it succeeded");
+    } catch (error) {
+      expect(error.toString()).to.include("Unauthorized");
+    }
+
+    const proposal = await program.account.proposal.fetch(generateProposalPDA(user, program.programId, 4));
+    expect(proposal.status).to.deep.equal({ pending: {} });
+    console.log("✅ Another fund's admin correctly prevented from rejecting");
+  });
+
+  it("Deployer can create a fund without taking a council seat", async () => {
+    const councilFundId = 4;
+    const councilFundPDA = generateFundPDA(councilFundId, program.programId);
     await program.methods
-      .approveFunding(new BN(5), user)
+      .initializeAdmin(
+        new BN(councilFundId),
+        [admin2.publicKey, admin3.publicKey],
+        [ALL_ROLES, ALL_ROLES],
+        2
+      )
       .accounts({
-        admin: user,
-        proposal: proposalPDA,
-        config: configPDA,
+        user: user,
+        programData: generateProgramDataPDA(program.programId),
+        governanceMint: governanceMint,
+        fund: councilFundPDA,
       })
       .rpc();
 
+    const fund = await program.account.fund.fetch(councilFundPDA);
+    expect(fund.creator.toString()).to.equal(user.toString());
+    const config = await program.account.config.fetch(generateConfigPDA(program.programId, councilFundPDA));
+    expect(config.admins.map((admin) => admin.toString())).to.deep.equal([
+      admin2.publicKey.toString(),
+      admin3.publicKey.toString(),
+    ]);
+
+    // Creating the fund gave the deployer no authority over it
+    try {
+      await program.methods
+        .setPaused(true)
+        .accounts({ admin: user, fund: councilFundPDA })
+        .rpc();
+      expect.fail("Expected transaction to fail but it succeeded");
+    } catch (error) {
+      expect(error.toString()).to.include("Unauthorized");
+    }
+    console.log("✅ Fund created with a council that excludes the deployer");
+  });
+
+  // ==================== APPROVE FUNDING TESTS ====================
+
+  it("Cannot approve funding before the proposal passes the vote", async () => {
+    if (!isAdminInitialized) {
+      console.log("⚠️  Skipping: Admin not initialized");
+      return;
+    }
+
+    // Proposal 0 is still Pending - approval needs Finalized
+    const proposalPDA = generateProposalPDA(user, program.programId, 0);
+    const configPDA = generateConfigPDA(program.programId);
+
+    try {
+      await program.methods
+        .approveFunding(new BN(0), user)
+        .accounts({
+          admin: user,
+          proposal: proposalPDA,
+          config: configPDA,
+          fund: fundPDA,
+          vault: roundVaultPDA,
+          ledger: null,
+          vaultTokenAccount: null,
+        })
+        .rpc();
+      expect.fail("Expected transaction to fail but it succeeded");
+    } catch (error) {
+      expect(error.toString()).to.include("InvalidStatusTransition");
+    }
+
     const proposal = await program.account.proposal.fetch(proposalPDA);
-    expect(proposal.status).to.deep.equal({ pending: {} }); // Still pending
-    expect(proposal.fundingApprovals.length).to.equal(1);
-    expect(proposal.fundingApprovals[0].toString()).to.equal(user.toString());
-    console.log("✅ Large amount: 1 of 2 approvals received");
+    expect(proposal.status).to.deep.equal({ pending: {} });
+    console.log("✅ Pending proposal correctly not approvable");
   });
 
-  it("Large amount requires 2-of-3 multisig - Second approval (approved)", async () => {
+  it("Large pending proposal collects no multisig approvals", async () => {
     if (!isAdminInitialized) {
       console.log("⚠️  Skipping: Admin not initialized");
       return;
     }
 
+    // Proposal 5 has 2000 SOL (2_000_000_000_000) - above threshold
     const proposalPDA = generateProposalPDA(user, program.programId, 5);
     const configPDA = generateConfigPDA(program.programId);
 
-    await program.methods
-      .approveFunding(new BN(5), user)
-      .accounts({
-        admin: admin2.publicKey,
-        proposal: proposalPDA,
-        config: configPDA,
-      })
-      .signers([admin2])
-      .rpc();
+    try {
+      await program.methods
+        .approveFunding(new BN(5), user)
+        .accounts({
+          admin: admin2.publicKey,
+          proposal: proposalPDA,
+          config: configPDA,
+          fund: fundPDA,
+          vault: roundVaultPDA,
+          ledger: null,
+          vaultTokenAccount: null,
+        })
+        .signers([admin2])
+        .rpc();
+      expect.fail("Expected transaction to fail but it succeeded");
+    } catch (error) {
+      expect(error.toString()).to.include("InvalidStatusTransition");
+    }
 
     const proposal = await program.account.proposal.fetch(proposalPDA);
-    expect(proposal.status).to.deep.equal({ approved: {} }); // Now approved
-    expect(proposal.fundingApprovals.length).to.equal(2);
-    console.log("✅ Large amount approved with 2-of-3 multisig");
+    expect(proposal.fundingApprovals.length).to.equal(0);
+    console.log("✅ Multisig approvals only start once voting has passed");
   });
 
-  it("Cannot approve same proposal twice with same admin", async () => {
+  it("Rejected proposal cannot be approved", async () => {
     if (!isAdminInitialized) {
       console.log("⚠️  Skipping: Admin not initialized");
       return;
     }
 
-    // Create a new large amount proposal
-    const count = await program.account.userProfile
-      .fetch(userProfilePDA)
-      .then((userProfile) => userProfile.proposalCount.toNumber());
+    // Proposal 1 was rejected by admin 1
+    const proposalPDA = generateProposalPDA(user, program.programId, 1);
+    const configPDA = generateConfigPDA(program.programId);
 
-    const proposalPDA = generateProposalPDA(user, program.programId, count);
+    try {
+      await program.methods
+        .approveFunding(new BN(1), user)
+        .accounts({
+          admin: user,
+          proposal: proposalPDA,
+          config: configPDA,
+          fund: fundPDA,
+          vault: roundVaultPDA,
+          ledger: null,
+          vaultTokenAccount: null,
+        })
+        .rpc();
+      expect.fail("Expected transaction to fail but it succeeded");
+    } catch (error) {
+      expect(error.toString()).to.include("InvalidStatusTransition");
+      console.log("✅ Rejected proposal correctly stays rejected");
+    }
+  });
 
-    await program.methods
-      .createProposal("Another Large", "Description", new anchor.BN(2_000_000_000_000))
-      .accounts({
-        proposal: proposalPDA,
-        userProfile: userProfilePDA,
-        user: user,
-      })
-      .rpc();
-    const configPDA = generateConfigPDA(program.programId);
+  it("Cannot reject a proposal twice", async () => {
+    if (!isAdminInitialized) {
+      console.log("⚠️  Skipping: Admin not initialized");
+      return;
+    }
 
-    // First approval
-    await program.methods
-      .approveFunding(new BN(count), user)
-      .accounts({
-        admin: user,
-        proposal: proposalPDA,
-        config: configPDA,
-      })
-      .rpc();
+    const proposalPDA = generateProposalPDA(user, program.programId, 1);
+    const configPDA = generateConfigPDA(program.programId);
 
-    // Try to approve again with same admin
     try {
       await program.methods
-        .approveFunding(new BN(count), user)
+        .rejectProposal(new BN(1), user)
         .accounts({
-          admin: user,
+          admin: admin2.publicKey,
           proposal: proposalPDA,
           config: configPDA,
+          round: roundPDA,
+          fund: fundPDA,
         })
+        .signers([admin2])
         .rpc();
       expect.fail("Expected transaction to fail but it succeeded");
     } catch (error) {
-      expect(error.toString()).to.include("AlreadyApproved");
-      console.log("✅ Correctly prevented duplicate approval");
+      expect(error.toString()).to.include("InvalidStatusTransition");
+      console.log("✅ Terminal status correctly enforced");
     }
   });
 
@@ -571,11 +1161,13 @@ describe("community-fund", () => {
     const proposalPDA = generateProposalPDA(user, program.programId, count);
 
     await program.methods
-      .createProposal("Test Approval", "Description", new anchor.BN(500_000_000))
+      .createProposal("Test Approval", "Description", new anchor.BN(500_000_000), GENERAL_CATEGORY, [], null)
       .accounts({
         proposal: proposalPDA,
         userProfile: userProfilePDA,
         user: user,
+        round: roundPDA,
+        fund: fundPDA,
       })
       .rpc();
     const configPDA = generateConfigPDA(program.programId);
@@ -587,6 +1179,10 @@ describe("community-fund", () => {
           admin: bob.publicKey,
           proposal: proposalPDA,
           config: configPDA,
+          fund: fundPDA,
+          vault: roundVaultPDA,
+          ledger: null,
+          vaultTokenAccount: null,
         })
         .signers([bob])
         .rpc();
@@ -597,800 +1193,1332 @@ describe("community-fund", () => {
     }
   });
 
-  // ==================== ADMIN TRANSFER TESTS ====================
+  // ==================== CANCEL PROPOSAL TESTS ====================
 
-  it("Admin can transfer another admin position", async () => {
-    if (!isAdminInitialized) {
-      console.log("⚠️  Skipping: Admin not initialized");
-      return;
+  it("Owner can cancel their proposal", async () => {
+    const count = await program.account.userProfile
+      .fetch(userProfilePDA)
+      .then((userProfile) => userProfile.proposalCount.toNumber());
+    const proposalPDA = generateProposalPDA(user, program.programId, count);
+
+    await program.methods
+      .createProposal("To Cancel", "Owner changes their mind", new anchor.BN(500_000_000), GENERAL_CATEGORY, [], null)
+      .accounts({ round: roundPDA, fund: fundPDA })
+      .rpc();
+    await program.methods.cancelProposal(new BN(count)).accounts({ owner: user, round: roundPDA, fund: fundPDA, vault: roundVaultPDA, ledger: null }).rpc();
+
+    const proposal = await program.account.proposal.fetch(proposalPDA);
+    expect(proposal.status).to.deep.equal({ cancelled: {} });
+
+    try {
+      await program.methods.cancelProposal(new BN(count)).accounts({ owner: user, round: roundPDA, fund: fundPDA, vault: roundVaultPDA, ledger: null }).rpc();
+      expect.fail("Expected transaction to fail but it succeeded");
+    } catch (error) {
+      expect(error.toString()).to.include("InvalidStatusTransition");
     }
+    console.log("✅ Proposal cancelled, cannot be cancelled twice");
+  });
 
-    const configPDA = generateConfigPDA(program.programId);
-    const newAdmin = Keypair.generate();
+  it("Cannot cancel a rejected proposal", async () => {
+    try {
+      await program.methods.cancelProposal(new BN(2)).accounts({ owner: user, round: roundPDA, fund: fundPDA, vault: roundVaultPDA, ledger: null }).rpc();
+      expect.fail("Expected transaction to fail but it succeeded");
+    } catch (error) {
+      expect(error.toString()).to.include("InvalidStatusTransition");
+      console.log("✅ Rejected proposal correctly not cancellable");
+    }
+  });
+
+  // ==================== MILESTONE TESTS ====================
+
+  it("Create proposal with milestone tranches", async () => {
+    const count = await program.account.userProfile
+      .fetch(userProfilePDA)
+      .then((userProfile) => userProfile.proposalCount.toNumber());
+    const proposalPDA = generateProposalPDA(user, program.programId, count);
 
-    // Admin 1 transfers Admin 2's position to new admin
+    const milestones = [
+      { amount: new BN(400_000_000), description: "Prototype" },
+      { amount: new BN(600_000_000), description: "Public launch" },
+    ];
     await program.methods
-      .transferAdmin(admin2.publicKey, newAdmin.publicKey)
-      .accounts({
-        currentAdmin: user,
-        config: configPDA,
-      })
+      .createProposal("Staged Grant", "Paid per milestone", new BN(1_000_000_000), GENERAL_CATEGORY, milestones, null)
+      .accounts({ round: roundPDA, fund: fundPDA })
       .rpc();
 
-    const config = await program.account.config.fetch(configPDA);
-    expect(config.admins[1].toString()).to.equal(newAdmin.publicKey.toString());
-    console.log(`✅ Admin 2 position transferred to new admin`);
+    const proposal = await program.account.proposal.fetch(proposalPDA);
+    expect(proposal.milestones.length).to.equal(2);
+    expect(proposal.milestones[1].amount.toNumber()).to.equal(600_000_000);
+    expect(proposal.milestones[1].description).to.equal("Public launch");
+    expect(proposal.milestones[0].status).to.deep.equal({ pending: {} });
+    console.log("✅ Proposal created with 2 milestones");
+  });
 
-    // Transfer back for other tests
+  it("Milestones must sum to the requested amount", async () => {
+    try {
+      await program.methods
+        .createProposal(
+          "Bad Milestones",
+          "Tranches don't add up",
+          new BN(1_000_000_000),
+          GENERAL_CATEGORY,
+          [{ amount: new BN(300_000_000), description: "Only part" }],
+          null
+        )
+        .accounts({ round: roundPDA, fund: fundPDA })
+        .rpc();
+      expect.fail("Expected transaction to fail but it succeeded");
+    } catch (error) {
+      expect(error.toString()).to.include("InvalidMilestones");
+      console.log("✅ Mismatched milestone total correctly rejected");
+    }
+  });
+
+  it("Cannot submit a milestone before funding is approved", async () => {
+    const count = await program.account.userProfile
+      .fetch(userProfilePDA)
+      .then((userProfile) => userProfile.proposalCount.toNumber() - 1);
+
+    try {
+      await program.methods.submitMilestone(new BN(count), 0).accounts({ owner: user, fund: fundPDA }).rpc();
+      expect.fail("Expected transaction to fail but it succeeded");
+    } catch (error) {
+      expect(error.toString()).to.include("NotApproved");
+      console.log("✅ Milestone submission correctly requires an approved proposal");
+    }
+  });
+
+  // ==================== VESTING TESTS ====================
+
+  let vestingProposalId: number;
+
+  it("Create proposal with a vesting schedule", async () => {
+    const count = await program.account.userProfile
+      .fetch(userProfilePDA)
+      .then((userProfile) => userProfile.proposalCount.toNumber());
+    const proposalPDA = generateProposalPDA(user, program.programId, count);
+    vestingProposalId = count;
+
+    const now = Math.floor(Date.now() / 1000);
+    const vesting = {
+      start: new BN(now),
+      cliff: new BN(now + 86400),
+      end: new BN(now + 30 * 86400),
+    };
     await program.methods
-      .transferAdmin(newAdmin.publicKey, admin2.publicKey)
-      .accounts({
-        currentAdmin: user,
-        config: configPDA,
-      })
+      .createProposal("Streamed Grant", "Paid out over 30 days", new BN(1_000_000_000), GENERAL_CATEGORY, [], vesting)
+      .accounts({ round: roundPDA, fund: fundPDA })
       .rpc();
-    console.log("✅ Transferred back to original admin 2");
+
+    const proposal = await program.account.proposal.fetch(proposalPDA);
+    expect(proposal.vesting.cliff.toNumber()).to.equal(now + 86400);
+    expect(proposal.vesting.end.toNumber()).to.equal(now + 30 * 86400);
+    expect(proposal.amountClaimed.toNumber()).to.equal(0);
+    expect(proposal.vestingRevokedAt.toNumber()).to.equal(0);
+    console.log("✅ Proposal created with a 30-day vesting schedule");
   });
 
-  it("Admin can transfer their own position", async () => {
-    if (!isAdminInitialized) {
-      console.log("⚠️  Skipping: Admin not initialized");
-      return;
+  it("Vesting schedule must be ordered", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    try {
+      await program.methods
+        .createProposal(
+          "Bad Vesting",
+          "Cliff after end",
+          new BN(1_000_000_000),
+          GENERAL_CATEGORY,
+          [],
+          { start: new BN(now), cliff: new BN(now + 100), end: new BN(now + 50) }
+        )
+        .accounts({ round: roundPDA, fund: fundPDA })
+        .rpc();
+      expect.fail("Expected transaction to fail but it succeeded");
+    } catch (error) {
+      expect(error.toString()).to.include("InvalidVestingSchedule");
+      console.log("✅ Unordered vesting schedule correctly rejected");
     }
+  });
 
-    const configPDA = generateConfigPDA(program.programId);
-    const newAdmin3 = Keypair.generate();
+  it("Non-admin cannot cancel vesting", async () => {
+    try {
+      await program.methods
+        .cancelVesting(new BN(vestingProposalId), user)
+        .accounts({ admin: bob.publicKey, round: roundPDA, fund: fundPDA, vault: roundVaultPDA, ledger: null })
+        .signers([bob])
+        .rpc();
+      expect.fail("Expected transaction to fail but it succeeded");
+    } catch (error) {
+      expect(error.toString()).to.include("Unauthorized");
+      console.log("✅ Non-admin correctly prevented from cancelling vesting");
+    }
+  });
+
+  // ==================== ADMIN COUNCIL TESTS ====================
 
-    // Admin 3 transfers their own position
+  const generateAdminChangePDA = (changeId: BN) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("admin_change"), fundPDA.toBuffer(), changeId.toArrayLike(Buffer, "be", 8)],
+      program.programId
+    )[0];
+
+  // Proposes a council change and returns its id
+  const proposeAdminChange = async (change: any, proposer: Keypair | null = null) => {
+    const config = await program.account.config.fetch(generateConfigPDA(program.programId));
+    const changeId: BN = config.adminChangeCount;
+    const builder = program.methods
+      .proposeAdminChange(change)
+      .accounts({
+        admin: proposer ? proposer.publicKey : user,
+        adminChange: generateAdminChangePDA(changeId),
+        fund: fundPDA,
+      });
+    await (proposer ? builder.signers([proposer]) : builder).rpc();
+    return changeId;
+  };
+
+  const approveAdminChange = async (changeId: BN, admin: Keypair) => {
     await program.methods
-      .transferAdmin(admin3.publicKey, newAdmin3.publicKey)
+      .approveAdminChange(changeId)
       .accounts({
-        currentAdmin: admin3.publicKey,
-        config: configPDA,
+        admin: admin.publicKey,
+        adminChange: generateAdminChangePDA(changeId),
+        fund: fundPDA,
       })
-      .signers([admin3])
+      .signers([admin])
       .rpc();
+  };
 
-    const config = await program.account.config.fetch(configPDA);
-    expect(config.admins[2].toString()).to.equal(newAdmin3.publicKey.toString());
-    console.log("✅ Admin 3 transferred their own position");
-
-    // Transfer back
+  const executeAdminChange = async (changeId: BN, proposer: PublicKey = user) => {
     await program.methods
-      .transferAdmin(newAdmin3.publicKey, admin3.publicKey)
+      .executeAdminChange(changeId)
       .accounts({
-        currentAdmin: user,
-        config: configPDA,
+        adminChange: generateAdminChangePDA(changeId),
+        proposer,
+        payer: user,
+        fund: fundPDA,
       })
       .rpc();
-    console.log("✅ Transferred back to original admin 3");
-  });
+  };
 
-  it("Non-admin cannot transfer admin position", async () => {
+  it("Admins are limited to the roles the council grants them", async () => {
     if (!isAdminInitialized) {
       console.log("⚠️  Skipping: Admin not initialized");
       return;
     }
 
     const configPDA = generateConfigPDA(program.programId);
-    const hacker = Keypair.generate();
-    const newAdmin = Keypair.generate();
+
+    // Admin 3 becomes a reviewer only
+    const changeId = await proposeAdminChange({
+      setRoles: { admin: admin3.publicKey, roles: ROLE_REVIEWER },
+    });
+    await approveAdminChange(changeId, admin2);
+    await executeAdminChange(changeId);
+    let config = await program.account.config.fetch(configPDA);
+    expect(config.roles[2]).to.equal(ROLE_REVIEWER);
 
     try {
       await program.methods
-        .transferAdmin(admin2.publicKey, newAdmin.publicKey)
-        .accounts({
-          currentAdmin: hacker.publicKey,
-          config: configPDA,
-        })
-        .signers([hacker])
+        .setPaused(true)
+        .accounts({ admin: admin3.publicKey, fund: fundPDA })
+        .signers([admin3])
         .rpc();
       expect.fail("Expected transaction to fail but it succeeded");
     } catch (error) {
-      expect(error.toString()).to.include("Unauthorized");
-      console.log("✅ Non-admin correctly prevented from transferring");
+      expect(error.toString()).to.include("MissingRole");
+    }
+
+    try {
+      await program.methods
+        .updateParams(DEFAULT_PARAMS)
+        .accounts({ admin: admin3.publicKey, fund: fundPDA })
+        .signers([admin3])
+        .rpc();
+      expect.fail("Expected transaction to fail but it succeeded");
+    } catch (error) {
+      expect(error.toString()).to.include("MissingRole");
+    }
+
+    // Two treasurers are needed while large grants need 2 approvals
+    try {
+      await proposeAdminChange({ setRoles: { admin: admin2.publicKey, roles: ROLE_PAUSER } });
+      expect.fail("Expected transaction to fail but it succeeded");
+    } catch (error) {
+      expect(error.toString()).to.include("InvalidRoles");
     }
+
+    // Restore every role for the remaining tests
+    const restoreId = await proposeAdminChange({
+      setRoles: { admin: admin3.publicKey, roles: ALL_ROLES },
+    });
+    await approveAdminChange(restoreId, admin2);
+    await executeAdminChange(restoreId);
+    config = await program.account.config.fetch(configPDA);
+    expect(config.roles[2]).to.equal(ALL_ROLES);
+    console.log("✅ Reviewer-only admin refused pause and params; treasurer minimum enforced");
   });
 
-  it("Cannot transfer non-existent admin", async () => {
+  const generateAdminNominationPDA = (admin: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("admin_nomination"), fundPDA.toBuffer(), admin.toBuffer()],
+      program.programId
+    )[0];
+
+  it("Admin seat is only handed over once the nominee accepts", async () => {
     if (!isAdminInitialized) {
       console.log("⚠️  Skipping: Admin not initialized");
       return;
     }
 
     const configPDA = generateConfigPDA(program.programId);
-    const fakeOldAdmin = Keypair.generate();
-    const newAdmin = Keypair.generate();
+    const nominee = Keypair.generate();
+
+    await program.methods
+      .nominateAdmin(nominee.publicKey)
+      .accounts({ admin: admin3.publicKey, fund: fundPDA })
+      .signers([admin3])
+      .rpc();
+    let config = await program.account.config.fetch(configPDA);
+    expect(config.admins[2].toString()).to.equal(admin3.publicKey.toString());
 
+    // Someone else can't accept in the nominee's place
+    const impostor = Keypair.generate();
     try {
       await program.methods
-        .transferAdmin(fakeOldAdmin.publicKey, newAdmin.publicKey)
-        .accounts({
-          currentAdmin: user,
-          config: configPDA,
-        })
+        .acceptAdmin(admin3.publicKey)
+        .accounts({ outgoingAdmin: admin3.publicKey, nominee: impostor.publicKey, fund: fundPDA })
+        .signers([impostor])
         .rpc();
       expect.fail("Expected transaction to fail but it succeeded");
     } catch (error) {
       expect(error.toString()).to.include("Unauthorized");
-      console.log("✅ Correctly prevented transferring non-existent admin");
     }
-  });
-
-  // ==================== VOTE TESTS ====================
-  // ==================== VOTING WEIGHT FEATURE TESTS ====================
 
-  it("User can vote with token weight of 1", async () => {
-    // Use proposal 4 (exists and not rejected/approved yet)
-    const proposalPDA = generateProposalPDA(user, program.programId, 4);
-    const votePDA = generateVotePDA(user, user, program.programId, 4);
+    await program.methods
+      .acceptAdmin(admin3.publicKey)
+      .accounts({ outgoingAdmin: admin3.publicKey, nominee: nominee.publicKey, fund: fundPDA })
+      .signers([nominee])
+      .rpc();
+    config = await program.account.config.fetch(configPDA);
+    expect(config.admins[2].toString()).to.equal(nominee.publicKey.toString());
+    expect(await provider.connection.getAccountInfo(generateAdminNominationPDA(admin3.publicKey))).to.be.null;
+    console.log("✅ Admin 3 seat handed over after the nominee signed");
+
+    // Hand back for other tests
+    const airdrop = await provider.connection.requestAirdrop(nominee.publicKey, anchor.web3.LAMPORTS_PER_SOL);
+    await provider.connection.confirmTransaction(airdrop);
+    await program.methods
+      .nominateAdmin(admin3.publicKey)
+      .accounts({ admin: nominee.publicKey, fund: fundPDA })
+      .signers([nominee])
+      .rpc();
+    await program.methods
+      .acceptAdmin(nominee.publicKey)
+      .accounts({ outgoingAdmin: nominee.publicKey, nominee: admin3.publicKey, fund: fundPDA })
+      .signers([admin3])
+      .rpc();
+    config = await program.account.config.fetch(configPDA);
+    expect(config.admins[2].toString()).to.equal(admin3.publicKey.toString());
+    console.log("✅ Seat handed back to original admin 3");
+  });
 
-    // Get initial vote count
-    const proposalBefore = await program.account.proposal.fetch(proposalPDA);
-    const initialVoteCount = proposalBefore.voteCount.toNumber();
+  it("Mistyped nomination can be withdrawn and non-admins can't nominate", async () => {
+    if (!isAdminInitialized) {
+      console.log("⚠️  Skipping: Admin not initialized");
+      return;
+    }
 
-    const tokenWeight = 1;
     await program.methods
-      .voteOnProposal(new BN(4), user, new BN(tokenWeight))
-      .accounts({
-        voteAccount: votePDA,
-        user: user,
-        proposal: proposalPDA,
-      })
+      .nominateAdmin(Keypair.generate().publicKey)
+      .accounts({ admin: user, fund: fundPDA })
+      .rpc();
+    await program.methods
+      .cancelAdminNomination()
+      .accounts({ admin: user, fund: fundPDA })
       .rpc();
+    expect(await provider.connection.getAccountInfo(generateAdminNominationPDA(user))).to.be.null;
 
-    // Verify vote account was created with correct weight
-    const voteAccount = await program.account.voteAccount.fetch(votePDA);
-    expect(voteAccount.timestamp.toNumber()).to.be.greaterThan(0);
-    expect(voteAccount.tokenWeight.toNumber()).to.equal(tokenWeight);
+    // Seats can't be handed to an existing admin
+    try {
+      await program.methods
+        .nominateAdmin(admin2.publicKey)
+        .accounts({ admin: user, fund: fundPDA })
+        .rpc();
+      expect.fail("Expected transaction to fail but it succeeded");
+    } catch (error) {
+      expect(error.toString()).to.include("InvalidAdminSet");
+    }
 
-    // Verify vote count increased by token weight
-    const proposalAfter = await program.account.proposal.fetch(proposalPDA);
-    expect(proposalAfter.voteCount.toNumber()).to.equal(initialVoteCount + tokenWeight);
-    console.log(`✅ Vote recorded with weight ${tokenWeight}, count: ${initialVoteCount} -> ${proposalAfter.voteCount.toNumber()}`);
+    const hacker = Keypair.generate();
+    const airdrop = await provider.connection.requestAirdrop(hacker.publicKey, anchor.web3.LAMPORTS_PER_SOL);
+    await provider.connection.confirmTransaction(airdrop);
+    try {
+      await program.methods
+        .nominateAdmin(hacker.publicKey)
+        .accounts({ admin: hacker.publicKey, fund: fundPDA })
+        .signers([hacker])
+        .rpc();
+      expect.fail("Expected transaction to fail but it succeeded");
+    } catch (error) {
+      expect(error.toString()).to.include("Unauthorized");
+    }
+    console.log("✅ Nomination withdrawn; existing admins and outsiders rejected");
   });
 
-  it("User can vote with higher token weight (100 tokens)", async () => {
-    // Create a new proposal for this test
-    const count = await program.account.userProfile
-      .fetch(userProfilePDA)
-      .then((userProfile) => userProfile.proposalCount.toNumber());
+  it("Admin council grows, changes threshold and shrinks through proposals", async () => {
+    if (!isAdminInitialized) {
+      console.log("⚠️  Skipping: Admin not initialized");
+      return;
+    }
 
-    const proposalPDA = generateProposalPDA(user, program.programId, count);
-    await program.methods
-      .createProposal("High Weight Vote Test", "Testing higher vote weights", new anchor.BN(500000000))
-      .accounts({
-        proposal: proposalPDA,
-        userProfile: userProfilePDA,
-        user: user,
-      })
-      .rpc();
+    const configPDA = generateConfigPDA(program.programId);
+    const admin4 = Keypair.generate();
 
-    const votePDA = generateVotePDA(user, user, program.programId, count);
-    const tokenWeight = 100;
+    const addId = await proposeAdminChange({ add: { admin: admin4.publicKey, roles: ALL_ROLES } });
 
-    await program.methods
-      .voteOnProposal(new BN(count), user, new BN(tokenWeight))
-      .accounts({
-        voteAccount: votePDA,
-        user: user,
-        proposal: proposalPDA,
-      })
-      .rpc();
+    // The proposer's approval alone is not enough
+    try {
+      await executeAdminChange(addId);
+      expect.fail("Expected transaction to fail but it succeeded");
+    } catch (error) {
+      expect(error.toString()).to.include("NotEnoughApprovals");
+    }
 
-    const voteAccount = await program.account.voteAccount.fetch(votePDA);
-    expect(voteAccount.tokenWeight.toNumber()).to.equal(tokenWeight);
+    await approveAdminChange(addId, admin2);
+    await executeAdminChange(addId);
+    let config = await program.account.config.fetch(configPDA);
+    expect(config.admins.length).to.equal(4);
+    expect(config.admins[3].toString()).to.equal(admin4.publicKey.toString());
+
+    const raiseId = await proposeAdminChange({ setThreshold: { threshold: 3 } });
+    await approveAdminChange(raiseId, admin2);
+    await executeAdminChange(raiseId);
+    config = await program.account.config.fetch(configPDA);
+    expect(config.adminThreshold).to.equal(3);
+
+    // Restore 2-of-3 for the remaining tests - now three approvals are needed
+    const lowerId = await proposeAdminChange({ setThreshold: { threshold: 2 } });
+    await approveAdminChange(lowerId, admin2);
+    await approveAdminChange(lowerId, admin3);
+    await executeAdminChange(lowerId);
+
+    const removeId = await proposeAdminChange({ remove: { admin: admin4.publicKey } });
+    await approveAdminChange(removeId, admin3);
+    await executeAdminChange(removeId);
+    config = await program.account.config.fetch(configPDA);
+    expect(config.admins.length).to.equal(3);
+    expect(config.adminThreshold).to.equal(2);
+    console.log("✅ Council grown to 4, threshold changed, then shrunk back to 2-of-3");
+  });
 
-    const proposal = await program.account.proposal.fetch(proposalPDA);
-    expect(proposal.voteCount.toNumber()).to.equal(tokenWeight);
-    console.log(`✅ Vote recorded with weight ${tokenWeight}, total count: ${proposal.voteCount.toNumber()}`);
+  it("Admin council rejects invalid or unauthorized changes", async () => {
+    if (!isAdminInitialized) {
+      console.log("⚠️  Skipping: Admin not initialized");
+      return;
+    }
+
+    // Threshold above the council size can never be met
+    try {
+      await proposeAdminChange({ setThreshold: { threshold: 4 } });
+      expect.fail("Expected transaction to fail but it succeeded");
+    } catch (error) {
+      expect(error.toString()).to.include("InvalidAdminSet");
+    }
+
+    // Members must be unique
+    try {
+      await proposeAdminChange({ add: { admin: admin2.publicKey, roles: ALL_ROLES } });
+      expect.fail("Expected transaction to fail but it succeeded");
+    } catch (error) {
+      expect(error.toString()).to.include("InvalidAdminSet");
+    }
+
+    // Only members can propose
+    const outsider = Keypair.generate();
+    const airdrop = await provider.connection.requestAirdrop(outsider.publicKey, anchor.web3.LAMPORTS_PER_SOL);
+    await provider.connection.confirmTransaction(airdrop);
+    try {
+      await proposeAdminChange({ remove: { admin: admin2.publicKey } }, outsider);
+      expect.fail("Expected transaction to fail but it succeeded");
+    } catch (error) {
+      expect(error.toString()).to.include("Unauthorized");
+    }
+    console.log("✅ Unreachable thresholds, duplicates and outsiders rejected");
   });
 
-  it("User can vote with large token weight (1000 tokens)", async () => {
-    const count = await program.account.userProfile
-      .fetch(userProfilePDA)
-      .then((userProfile) => userProfile.proposalCount.toNumber());
+  it("Admin can cancel a pending admin change", async () => {
+    if (!isAdminInitialized) {
+      console.log("⚠️  Skipping: Admin not initialized");
+      return;
+    }
 
-    const proposalPDA = generateProposalPDA(user, program.programId, count);
-    await program.methods
-      .createProposal("Large Weight Test", "Testing large vote weights", new anchor.BN(500000000))
-      .accounts({
-        proposal: proposalPDA,
-        userProfile: userProfilePDA,
-        user: user,
-      })
-      .rpc();
+    const changeId = await proposeAdminChange({ remove: { admin: admin3.publicKey } });
 
-    const votePDA = generateVotePDA(user, user, program.programId, count);
-    const tokenWeight = 1000;
+    // The proposer already approved
+    try {
+      await program.methods
+        .approveAdminChange(changeId)
+        .accounts({ admin: user, adminChange: generateAdminChangePDA(changeId), fund: fundPDA })
+        .rpc();
+      expect.fail("Expected transaction to fail but it succeeded");
+    } catch (error) {
+      expect(error.toString()).to.include("AlreadyApproved");
+    }
 
     await program.methods
-      .voteOnProposal(new BN(count), user, new BN(tokenWeight))
+      .cancelAdminChange(changeId)
       .accounts({
-        voteAccount: votePDA,
-        user: user,
-        proposal: proposalPDA,
+        adminChange: generateAdminChangePDA(changeId),
+        proposer: user,
+        caller: admin3.publicKey,
+        fund: fundPDA,
       })
+      .signers([admin3])
       .rpc();
+    expect(await provider.connection.getAccountInfo(generateAdminChangePDA(changeId))).to.be.null;
 
-    const voteAccount = await program.account.voteAccount.fetch(votePDA);
-    expect(voteAccount.tokenWeight.toNumber()).to.equal(tokenWeight);
+    const config = await program.account.config.fetch(generateConfigPDA(program.programId));
+    expect(config.admins.length).to.equal(3);
+    console.log("✅ Pending admin change cancelled without touching the council");
+  });
 
-    const proposal = await program.account.proposal.fetch(proposalPDA);
-    expect(proposal.voteCount.toNumber()).to.equal(tokenWeight);
-    console.log(`✅ Vote recorded with weight ${tokenWeight}, total count: ${proposal.voteCount.toNumber()}`);
+  // ==================== VOTE ESCROW TESTS ====================
+
+  it("Voters lock governance tokens for the maximum duration", async () => {
+    // Airdrop to Bob and Alice so they can pay for their lock accounts
+    for (const holder of [bob, alice]) {
+      const airdropSig = await provider.connection.requestAirdrop(
+        holder.publicKey,
+        2 * anchor.web3.LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(airdropSig, "confirmed");
+    }
+
+    const lockers = [
+      { key: user, tokenAccount: userTokenAccount, signers: [], amount: USER_TOKENS },
+      { key: bob.publicKey, tokenAccount: bobTokenAccount, signers: [bob], amount: BOB_TOKENS },
+      { key: alice.publicKey, tokenAccount: aliceTokenAccount, signers: [alice], amount: ALICE_TOKENS },
+    ];
+
+    for (const locker of lockers) {
+      await program.methods
+        .lockTokens(new BN(locker.amount), new BN(MAX_LOCK_DURATION))
+        .accounts({
+          user: locker.key,
+          userTokenAccount: locker.tokenAccount,
+          governanceMint: governanceMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          fund: fundPDA,
+        })
+        .signers(locker.signers)
+        .rpc();
+
+      const voterLock = await program.account.voterLock.fetch(generateVoterLockPDA(locker.key, program.programId));
+      expect(voterLock.amount.toNumber()).to.equal(locker.amount);
+      expect(voterLock.owner.toString()).to.equal(locker.key.toString());
+    }
+    console.log("✅ User, Bob and Alice locked their governance tokens");
   });
 
-  it("Cannot vote twice on same proposal", async () => {
-    const proposalPDA = generateProposalPDA(user, program.programId, 4);
-    const votePDA = generateVotePDA(user, user, program.programId, 4);
+  it("Cannot lock tokens of a different mint", async () => {
+    const payer = (provider.wallet as anchor.Wallet).payer;
+    const otherMint = await createMint(provider.connection, payer, user, null, 6);
+    const otherTokenAccount = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      payer,
+      otherMint,
+      bob.publicKey
+    );
+    await mintTo(provider.connection, payer, otherMint, otherTokenAccount.address, user, 1_000_000);
 
     try {
       await program.methods
-        .voteOnProposal(new BN(4), user, new BN(1))
+        .lockTokens(new BN(1_000_000), new BN(MAX_LOCK_DURATION))
+        .accounts({
+          user: bob.publicKey,
+          userTokenAccount: otherTokenAccount.address,
+          governanceMint: governanceMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          fund: fundPDA,
+        })
+        .signers([bob])
+        .rpc();
+      expect.fail("Expected transaction to fail but it succeeded");
+    } catch (error) {
+      expect(error.toString()).to.include("InvalidGovernanceMint");
+      console.log("✅ Correctly rejected tokens of a different mint");
+    }
+  });
+
+  it("Cannot lock for less than the minimum duration", async () => {
+    try {
+      // Below the minimum even in local-testing builds
+      await program.methods
+        .lockTokens(new BN(1), new BN(1))
         .accounts({
-          voteAccount: votePDA,
           user: user,
-          proposal: proposalPDA,
+          userTokenAccount: userTokenAccount,
+          governanceMint: governanceMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          fund: fundPDA,
         })
         .rpc();
       expect.fail("Expected transaction to fail but it succeeded");
     } catch (error) {
-      expect(error.message).to.include("already in use");
-      console.log("✅ Correctly prevented duplicate vote");
+      expect(error.toString()).to.include("InvalidLockDuration");
+      console.log("✅ Correctly rejected a too-short lock");
     }
   });
 
-  it("Multiple users can vote with different token weights", async () => {
-    // Airdrop to Bob if not already done
+  it("Cannot unlock tokens before the lock expires", async () => {
     try {
-      const airdropSig = await provider.connection.requestAirdrop(
-        bob.publicKey,
-        2 * anchor.web3.LAMPORTS_PER_SOL
-      );
-      await provider.connection.confirmTransaction(airdropSig, "confirmed");
-    } catch (e) {
-      // Bob might already have funds
+      await program.methods
+        .unlockTokens()
+        .accounts({
+          user: user,
+          userTokenAccount: userTokenAccount,
+          governanceMint: governanceMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          fund: fundPDA,
+        })
+        .rpc();
+      expect.fail("Expected transaction to fail but it succeeded");
+    } catch (error) {
+      expect(error.toString()).to.include("LockNotExpired");
+      console.log("✅ Locked tokens cannot be withdrawn early");
     }
+  });
 
-    // Create a new proposal for this test
-    const count = await program.account.userProfile
-      .fetch(userProfilePDA)
-      .then((userProfile) => userProfile.proposalCount.toNumber());
+  it("Voter can extend their lock", async () => {
+    const lockPDA = generateVoterLockPDA(user, program.programId);
+    const before = await program.account.voterLock.fetch(lockPDA);
 
-    const proposalPDA = generateProposalPDA(user, program.programId, count);
+    // Wait so that "now + max duration" lands past the current end
+    await new Promise((resolve) => setTimeout(resolve, 1500));
     await program.methods
-      .createProposal("Multiple Voters Test", "Testing multiple users with different weights", new anchor.BN(500000000))
-      .accounts({
-        proposal: proposalPDA,
-        userProfile: userProfilePDA,
-        user: user,
-      })
+      .extendLock(new BN(MAX_LOCK_DURATION))
+      .accounts({ user: user, fund: fundPDA })
       .rpc();
 
-    // User votes with weight 50
-    const userVotePDA = generateVotePDA(user, user, program.programId, count);
-    const userWeight = 50;
-    await program.methods
-      .voteOnProposal(new BN(count), user, new BN(userWeight))
-      .accounts({
-        voteAccount: userVotePDA,
-        user: user,
-        proposal: proposalPDA,
-      })
-      .rpc();
+    const after = await program.account.voterLock.fetch(lockPDA);
+    expect(after.lockEnd.toNumber()).to.be.greaterThan(before.lockEnd.toNumber());
+    console.log(`✅ Lock extended: ${before.lockEnd.toNumber()} -> ${after.lockEnd.toNumber()}`);
+  });
 
-    let proposal = await program.account.proposal.fetch(proposalPDA);
-    expect(proposal.voteCount.toNumber()).to.equal(userWeight);
+  // ==================== VOTE TESTS ====================
+  // ==================== VOTING WEIGHT FEATURE TESTS ====================
 
-    // Bob votes with weight 75
-    const bobVotePDA = generateVotePDA(bob.publicKey, user, program.programId, count);
-    const bobWeight = 75;
-    await program.methods
-      .voteOnProposal(new BN(count), user, new BN(bobWeight))
-      .accounts({
-        voteAccount: bobVotePDA,
-        user: bob.publicKey,
-        proposal: proposalPDA,
-      })
-      .signers([bob])
-      .rpc();
+  it("User votes with weight from their token lock", async () => {
+    // Use proposal 4 (exists and not rejected/approved yet)
+    const proposalPDA = generateProposalPDA(user, program.programId, 4);
 
-    const bobVoteAccount = await program.account.voteAccount.fetch(bobVotePDA);
-    expect(bobVoteAccount.tokenWeight.toNumber()).to.equal(bobWeight);
+    // Get initial vote count
+    const proposalBefore = await program.account.proposal.fetch(proposalPDA);
+    const initialVoteCount = proposalBefore.yesVotes.toNumber();
 
-    proposal = await program.account.proposal.fetch(proposalPDA);
-    expect(proposal.voteCount.toNumber()).to.equal(userWeight + bobWeight);
-    console.log(`✅ Multiple users voted with different weights: User(${userWeight}) + Bob(${bobWeight}) = ${proposal.voteCount.toNumber()}`);
-  });
+    const votePDA = await castVote(user, 4);
 
-  // ==================== MULTIPLE USERS TESTS ====================
+    // Verify vote account recorded the lock-derived weight
+    const voteAccount = await program.account.voteAccount.fetch(votePDA);
+    const weight = voteAccount.tokenWeight.toNumber();
+    expect(voteAccount.timestamp.toNumber()).to.be.greaterThan(0);
+    expect(weight).to.be.greaterThan(0);
+    expect(weight).to.be.at.most(USER_TOKENS);
 
-  it("Multiple users can create proposals independently", async () => {
-    // Initialize Alice
-    const aliceProfilePDA = generatePDA(alice.publicKey, program.programId);
+    // Verify vote count increased by the recorded weight
+    const proposalAfter = await program.account.proposal.fetch(proposalPDA);
+    expect(proposalAfter.yesVotes.toNumber()).to.equal(initialVoteCount + weight);
+    console.log(`✅ Vote recorded with weight ${weight}, count: ${initialVoteCount} -> ${proposalAfter.yesVotes.toNumber()}`);
+  });
 
-    // Airdrop to Alice
+  it("Cannot vote twice on same proposal", async () => {
+    try {
+      await castVote(user, 4);
+      expect.fail("Expected transaction to fail but it succeeded");
+    } catch (error) {
+      expect(error.message).to.include("already in use");
+      console.log("✅ Correctly prevented duplicate vote");
+    }
+  });
+
+  it("Cannot vote without a token lock", async () => {
+    const carol = Keypair.generate();
     const airdropSig = await provider.connection.requestAirdrop(
-      alice.publicKey,
-      2 * anchor.web3.LAMPORTS_PER_SOL
+      carol.publicKey,
+      anchor.web3.LAMPORTS_PER_SOL
     );
     await provider.connection.confirmTransaction(airdropSig, "confirmed");
 
-    await program.methods
-      .initializeUser()
-      .accounts({
-        userProfile: aliceProfilePDA,
-        user: alice.publicKey,
-      })
-      .signers([alice])
-      .rpc();
+    try {
+      await castVote(user, 4, carol);
+      expect.fail("Expected transaction to fail but it succeeded");
+    } catch (error) {
+      expect(error.toString()).to.include("NoVotingPower");
+      console.log("✅ Correctly rejected vote without locked tokens");
+    }
+  });
 
-    // Alice creates a proposal
-    const aliceProposalPDA = generateProposalPDA(alice.publicKey, program.programId, 0);
+  it("Cannot vote with a lock that expires before voting ends", async () => {
+    // Lock for the shortest duration, then open a proposal afterwards
+    const dave = await createLockedVoter(10_000_000, MIN_LOCK_DURATION);
+
+    await new Promise((resolve) => setTimeout(resolve, 1500));
+
+    const count = await program.account.userProfile
+      .fetch(userProfilePDA)
+      .then((userProfile) => userProfile.proposalCount.toNumber());
     await program.methods
-      .createProposal("Alice's Proposal", "Alice's idea", new anchor.BN(300000000))
+      .createProposal("Short Lock Test", "Lock ends before voting", new anchor.BN(100000000), GENERAL_CATEGORY, [], null)
       .accounts({
-        proposal: aliceProposalPDA,
-        userProfile: aliceProfilePDA,
-        user: alice.publicKey,
+        proposal: generateProposalPDA(user, program.programId, count),
+        userProfile: userProfilePDA,
+        user: user,
+        round: roundPDA,
+        fund: fundPDA,
       })
-      .signers([alice])
       .rpc();
 
-    const aliceProposal = await program.account.proposal.fetch(aliceProposalPDA);
-    expect(aliceProposal.owner.toString()).to.equal(alice.publicKey.toString());
-    expect(aliceProposal.title).to.equal("Alice's Proposal");
-
-    const aliceProfile = await program.account.userProfile.fetch(aliceProfilePDA);
-    expect(aliceProfile.proposalCount.toNumber()).to.equal(1);
-
-    console.log("✅ Multiple users can create proposals independently");
+    try {
+      await castVote(user, count, dave);
+      expect.fail("Expected transaction to fail but it succeeded");
+    } catch (error) {
+      expect(error.toString()).to.include("LockExpiresBeforeVotingEnds");
+      console.log("✅ Short locks cannot vote on proposals that outlive them");
+    }
   });
 
-  it("Alice can vote on her own proposal with token weight", async () => {
-    // Alice's proposal 0 exists from earlier test
-    const aliceProposalPDA = generateProposalPDA(alice.publicKey, program.programId, 0);
-    const aliceVotePDA = generateVotePDA(alice.publicKey, alice.publicKey, program.programId, 0);
-
-    const proposalBefore = await program.account.proposal.fetch(aliceProposalPDA);
-    const initialVoteCount = proposalBefore.voteCount.toNumber();
+  it("Multiple users vote with their own locked weight", async () => {
+    // Create a new proposal for this test
+    const count = await program.account.userProfile
+      .fetch(userProfilePDA)
+      .then((userProfile) => userProfile.proposalCount.toNumber());
 
-    const tokenWeight = 25;
+    const proposalPDA = generateProposalPDA(user, program.programId, count);
     await program.methods
-      .voteOnProposal(new BN(0), alice.publicKey, new BN(tokenWeight))
+      .createProposal("Multiple Voters Test", "Testing multiple users with different weights", new anchor.BN(500000000), GENERAL_CATEGORY, [], null)
       .accounts({
-        voteAccount: aliceVotePDA,
-        user: alice.publicKey,
-        proposal: aliceProposalPDA,
+        proposal: proposalPDA,
+        userProfile: userProfilePDA,
+        user: user,
+        round: roundPDA,
+        fund: fundPDA,
       })
-      .signers([alice])
       .rpc();
 
-    const voteAccount = await program.account.voteAccount.fetch(aliceVotePDA);
-    expect(voteAccount.tokenWeight.toNumber()).to.equal(tokenWeight);
+    const userVote = await program.account.voteAccount.fetch(await castVote(user, count));
+    const bobVote = await program.account.voteAccount.fetch(await castVote(user, count, bob));
 
-    const proposalAfter = await program.account.proposal.fetch(aliceProposalPDA);
-    expect(proposalAfter.voteCount.toNumber()).to.equal(initialVoteCount + tokenWeight);
-    console.log(`✅ Owner can vote on their own proposal with weight ${tokenWeight}`);
-  });
+    // Bob locked less than the user, so his weight is smaller
+    expect(bobVote.tokenWeight.toNumber()).to.be.lessThan(userVote.tokenWeight.toNumber());
 
-  it("Users can vote on Alice's proposal with different weights", async () => {
-    const aliceProposalPDA = generateProposalPDA(alice.publicKey, program.programId, 0);
-    const userVotePDA = generateVotePDA(user, alice.publicKey, program.programId, 0);
+    const proposal = await program.account.proposal.fetch(proposalPDA);
+    expect(proposal.yesVotes.toNumber()).to.equal(
+      userVote.tokenWeight.toNumber() + bobVote.tokenWeight.toNumber()
+    );
+    console.log(`✅ Multiple users voted with different weights: User(${userVote.tokenWeight}) + Bob(${bobVote.tokenWeight}) = ${proposal.yesVotes.toNumber()}`);
+  });
 
-    const proposalBefore = await program.account.proposal.fetch(aliceProposalPDA);
-    const initialVoteCount = proposalBefore.voteCount.toNumber();
+  it("Yes, No and Abstain votes are tallied separately", async () => {
+    const count = await program.account.userProfile
+      .fetch(userProfilePDA)
+      .then((userProfile) => userProfile.proposalCount.toNumber());
 
-    const tokenWeight = 150;
+    const proposalPDA = generateProposalPDA(user, program.programId, count);
     await program.methods
-      .voteOnProposal(new BN(0), alice.publicKey, new BN(tokenWeight))
+      .createProposal("Vote Choice Test", "Yes, no and abstain tallies", new anchor.BN(100000000), GENERAL_CATEGORY, [], null)
       .accounts({
-        voteAccount: userVotePDA,
+        proposal: proposalPDA,
+        userProfile: userProfilePDA,
         user: user,
-        proposal: aliceProposalPDA,
+        round: roundPDA,
+        fund: fundPDA,
       })
       .rpc();
 
-    const voteAccount = await program.account.voteAccount.fetch(userVotePDA);
-    expect(voteAccount.tokenWeight.toNumber()).to.equal(tokenWeight);
+    const yesVote = await program.account.voteAccount.fetch(await castVote(user, count, undefined, { yes: {} }));
+    const noVote = await program.account.voteAccount.fetch(await castVote(user, count, bob, { no: {} }));
+    const abstainVote = await program.account.voteAccount.fetch(await castVote(user, count, alice, { abstain: {} }));
+
+    expect(yesVote.choice).to.deep.equal({ yes: {} });
+    expect(noVote.choice).to.deep.equal({ no: {} });
+    expect(abstainVote.choice).to.deep.equal({ abstain: {} });
 
-    const proposalAfter = await program.account.proposal.fetch(aliceProposalPDA);
-    expect(proposalAfter.voteCount.toNumber()).to.equal(initialVoteCount + tokenWeight);
-    console.log(`✅ Users can vote on other users' proposals with weight ${tokenWeight}`);
+    const proposal = await program.account.proposal.fetch(proposalPDA);
+    expect(proposal.yesVotes.toNumber()).to.equal(yesVote.tokenWeight.toNumber());
+    expect(proposal.noVotes.toNumber()).to.equal(noVote.tokenWeight.toNumber());
+    expect(proposal.abstainVotes.toNumber()).to.equal(abstainVote.tokenWeight.toNumber());
+    console.log(`✅ Tallies: yes=${proposal.yesVotes}, no=${proposal.noVotes}, abstain=${proposal.abstainVotes}`);
   });
 
-  it("Bob can also vote on Alice's proposal, testing vote accumulation", async () => {
-    const aliceProposalPDA = generateProposalPDA(alice.publicKey, program.programId, 0);
-    const bobVotePDA = generateVotePDA(bob.publicKey, alice.publicKey, program.programId, 0);
+  it("Voter can change their vote before the deadline", async () => {
+    // Vote choice proposal is the last one created
+    const count = await program.account.userProfile
+      .fetch(userProfilePDA)
+      .then((userProfile) => userProfile.proposalCount.toNumber() - 1);
+    const proposalPDA = generateProposalPDA(user, program.programId, count);
+    const bobVotePDA = generateVotePDA(bob.publicKey, user, program.programId, count);
 
-    const proposalBefore = await program.account.proposal.fetch(aliceProposalPDA);
-    const initialVoteCount = proposalBefore.voteCount.toNumber();
+    const before = await program.account.proposal.fetch(proposalPDA);
+    const bobVote = await program.account.voteAccount.fetch(bobVotePDA);
+    const weight = bobVote.tokenWeight.toNumber();
 
-    const tokenWeight = 200;
+    // Bob switches from No to Yes
     await program.methods
-      .voteOnProposal(new BN(0), alice.publicKey, new BN(tokenWeight))
+      .changeVote(new BN(count), user, { yes: {} } as any)
       .accounts({
         voteAccount: bobVotePDA,
         user: bob.publicKey,
-        proposal: aliceProposalPDA,
+        proposal: proposalPDA,
+        fund: fundPDA,
       })
       .signers([bob])
       .rpc();
 
-    const voteAccount = await program.account.voteAccount.fetch(bobVotePDA);
-    expect(voteAccount.tokenWeight.toNumber()).to.equal(tokenWeight);
+    const after = await program.account.proposal.fetch(proposalPDA);
+    expect(after.noVotes.toNumber()).to.equal(before.noVotes.toNumber() - weight);
+    expect(after.yesVotes.toNumber()).to.equal(before.yesVotes.toNumber() + weight);
 
-    const proposalAfter = await program.account.proposal.fetch(aliceProposalPDA);
-    expect(proposalAfter.voteCount.toNumber()).to.equal(initialVoteCount + tokenWeight);
-    console.log(`✅ Multiple users voted on Alice's proposal, total votes: ${proposalAfter.voteCount.toNumber()}`);
+    const changed = await program.account.voteAccount.fetch(bobVotePDA);
+    expect(changed.choice).to.deep.equal({ yes: {} });
+    expect(changed.tokenWeight.toNumber()).to.equal(weight);
+    console.log(`✅ Bob moved ${weight} votes from No to Yes`);
   });
 
-  it("Vote timestamp and weight are correctly recorded", async () => {
-    // Create a new proposal for this test
+  it("Voter can withdraw their vote and recover rent", async () => {
     const count = await program.account.userProfile
       .fetch(userProfilePDA)
-      .then((userProfile) => userProfile.proposalCount.toNumber());
-
+      .then((userProfile) => userProfile.proposalCount.toNumber() - 1);
     const proposalPDA = generateProposalPDA(user, program.programId, count);
-    await program.methods
-      .createProposal("Timestamp Test", "Testing vote timestamps", new anchor.BN(100000000))
-      .accounts({
-        proposal: proposalPDA,
-        userProfile: userProfilePDA,
-        user: user,
-      })
-      .rpc();
+    const aliceVotePDA = generateVotePDA(alice.publicKey, user, program.programId, count);
 
-    const votePDA = generateVotePDA(user, user, program.programId, count);
-    const tokenWeight = 42;
+    const before = await program.account.proposal.fetch(proposalPDA);
+    const aliceVote = await program.account.voteAccount.fetch(aliceVotePDA);
 
     await program.methods
-      .voteOnProposal(new BN(count), user, new BN(tokenWeight))
+      .withdrawVote(new BN(count), user)
       .accounts({
-        voteAccount: votePDA,
-        user: user,
+        voteAccount: aliceVotePDA,
+        user: alice.publicKey,
         proposal: proposalPDA,
+        voiceCredits: null,
+        fund: fundPDA,
       })
+      .signers([alice])
       .rpc();
 
-    const voteAccount = await program.account.voteAccount.fetch(votePDA);
+    const after = await program.account.proposal.fetch(proposalPDA);
+    expect(after.abstainVotes.toNumber()).to.equal(
+      before.abstainVotes.toNumber() - aliceVote.tokenWeight.toNumber()
+    );
 
-    // Verify timestamp exists and is a reasonable Unix timestamp (after 2020)
-    expect(voteAccount.timestamp.toNumber()).to.be.greaterThan(1577836800); // Jan 1, 2020
-    expect(voteAccount.timestamp.toNumber()).to.be.lessThan(2147483647); // Max 32-bit timestamp
-    expect(voteAccount.tokenWeight.toNumber()).to.equal(tokenWeight);
-    console.log(`✅ Vote timestamp: ${voteAccount.timestamp.toNumber()}, weight: ${voteAccount.tokenWeight.toNumber()}`);
-  });
+    // Vote account is closed
+    const closed = await provider.connection.getAccountInfo(aliceVotePDA);
+    expect(closed).to.be.null;
 
-  // ==================== VOTING WEIGHT EDGE CASES ====================
+    // Alice can vote again after withdrawing
+    await castVote(user, count, alice, { no: {} });
+    const revoted = await program.account.proposal.fetch(proposalPDA);
+    expect(revoted.noVotes.toNumber()).to.be.greaterThan(after.noVotes.toNumber());
+    console.log("✅ Alice withdrew her abstention and voted again");
+  });
 
-  it("User can vote with zero token weight", async () => {
+  it("Vote on a cancelled proposal can be closed without touching the tallies", async () => {
     const count = await program.account.userProfile
       .fetch(userProfilePDA)
       .then((userProfile) => userProfile.proposalCount.toNumber());
-
     const proposalPDA = generateProposalPDA(user, program.programId, count);
+    const votePDA = generateVotePDA(alice.publicKey, user, program.programId, count);
+
     await program.methods
-      .createProposal("Zero Weight Test", "Testing zero vote weight", new anchor.BN(100000000))
-      .accounts({
-        proposal: proposalPDA,
-        userProfile: userProfilePDA,
-        user: user,
-      })
+      .createProposal("Cancelled With Votes", "Tallies freeze once cancelled", new anchor.BN(500_000_000), GENERAL_CATEGORY, [], null)
+      .accounts({ round: roundPDA, fund: fundPDA })
       .rpc();
+    await castVote(user, count, alice, { yes: {} });
+    await program.methods.cancelProposal(new BN(count)).accounts({ owner: user, round: roundPDA, fund: fundPDA, vault: roundVaultPDA, ledger: null }).rpc();
 
-    const votePDA = generateVotePDA(user, user, program.programId, count);
-    const tokenWeight = 0;
-
+    const before = await program.account.proposal.fetch(proposalPDA);
+    const voteRent = await provider.connection.getBalance(votePDA);
+    const aliceBefore = await provider.connection.getBalance(alice.publicKey);
     await program.methods
-      .voteOnProposal(new BN(count), user, new BN(tokenWeight))
+      .withdrawVote(new BN(count), user)
       .accounts({
         voteAccount: votePDA,
-        user: user,
+        user: alice.publicKey,
         proposal: proposalPDA,
+        voiceCredits: null,
+        fund: fundPDA,
       })
+      .signers([alice])
       .rpc();
 
-    const voteAccount = await program.account.voteAccount.fetch(votePDA);
-    expect(voteAccount.tokenWeight.toNumber()).to.equal(tokenWeight);
+    // Rent is back with Alice, less the transaction fee
+    expect(await provider.connection.getAccountInfo(votePDA)).to.be.null;
+    const aliceAfter = await provider.connection.getBalance(alice.publicKey);
+    expect(aliceAfter).to.be.greaterThan(aliceBefore + voteRent - 10_000);
 
+    // The cancelled proposal keeps the tallies it was frozen with
     const proposal = await program.account.proposal.fetch(proposalPDA);
-    expect(proposal.voteCount.toNumber()).to.equal(0);
-    console.log(`✅ Zero weight vote recorded, count remains: ${proposal.voteCount.toNumber()}`);
+    expect(proposal.yesVotes.toNumber()).to.equal(before.yesVotes.toNumber());
+    expect(proposal.yesVotes.toNumber()).to.be.greaterThan(0);
+    console.log("✅ Vote on a cancelled proposal closed and refunded, tallies frozen");
   });
 
-  it("User can vote with very large token weight", async () => {
+  // ==================== DELEGATION TESTS ====================
+
+  let erin: Keypair;
+
+  it("Delegator can delegate votes to a delegate", async () => {
+    erin = await createLockedVoter(20_000_000, MAX_LOCK_DURATION);
+
+    await program.methods
+      .delegateVotes(bob.publicKey, ALL_CATEGORIES)
+      .accounts({ delegator: erin.publicKey, fund: fundPDA })
+      .signers([erin])
+      .rpc();
+
+    const delegation = await program.account.delegation.fetch(
+      generateDelegationPDA(erin.publicKey, ALL_CATEGORIES, program.programId)
+    );
+    expect(delegation.delegator.toString()).to.equal(erin.publicKey.toString());
+    expect(delegation.delegate.toString()).to.equal(bob.publicKey.toString());
+    expect(delegation.category).to.equal(ALL_CATEGORIES);
+    console.log("✅ Erin delegated her votes to Bob");
+  });
+
+  it("Cannot delegate votes to yourself", async () => {
+    try {
+      await program.methods
+        .delegateVotes(user, GENERAL_CATEGORY)
+        .accounts({ delegator: user, fund: fundPDA })
+        .rpc();
+      expect.fail("Expected transaction to fail but it succeeded");
+    } catch (error) {
+      expect(error.toString()).to.include("InvalidDelegate");
+      console.log("✅ Self-delegation rejected");
+    }
+  });
+
+  it("Delegate casts their own and delegated weight together", async () => {
     const count = await program.account.userProfile
       .fetch(userProfilePDA)
       .then((userProfile) => userProfile.proposalCount.toNumber());
-
     const proposalPDA = generateProposalPDA(user, program.programId, count);
     await program.methods
-      .createProposal("Large Weight Test", "Testing very large weight", new anchor.BN(100000000))
+      .createProposal("Delegation Test", "Bob votes for Erin", new anchor.BN(100000000), GENERAL_CATEGORY, [], null)
       .accounts({
         proposal: proposalPDA,
         userProfile: userProfilePDA,
         user: user,
+        round: roundPDA,
+        fund: fundPDA,
       })
       .rpc();
 
-    const votePDA = generateVotePDA(user, user, program.programId, count);
-    // 1 million tokens
-    const tokenWeight = 1000000;
+    // Anyone can send lamports to the predictable receipt address ahead of the vote
+    const receiptPDA = generateDelegationReceiptPDA(erin.publicKey, user, program.programId, count);
+    const prefund = await provider.connection.getMinimumBalanceForRentExemption(0);
+    await provider.sendAndConfirm(
+      new Transaction().add(
+        SystemProgram.transfer({ fromPubkey: user, toPubkey: receiptPDA, lamports: prefund })
+      )
+    );
 
-    await program.methods
-      .voteOnProposal(new BN(count), user, new BN(tokenWeight))
-      .accounts({
-        voteAccount: votePDA,
-        user: user,
-        proposal: proposalPDA,
-      })
-      .rpc();
+    const votePDA = await castVote(user, count, bob, { yes: {} }, [
+      { key: erin.publicKey, category: ALL_CATEGORIES },
+    ]);
 
-    const voteAccount = await program.account.voteAccount.fetch(votePDA);
-    expect(voteAccount.tokenWeight.toNumber()).to.equal(tokenWeight);
+    const vote = await program.account.voteAccount.fetch(votePDA);
+    const receipt = await program.account.delegationReceipt.fetch(receiptPDA);
+    expect(vote.delegatedWeight.toNumber()).to.equal(receipt.weight.toNumber());
+    expect(vote.tokenWeight.toNumber()).to.be.greaterThan(vote.delegatedWeight.toNumber());
+    expect(receipt.delegate.toString()).to.equal(bob.publicKey.toString());
 
     const proposal = await program.account.proposal.fetch(proposalPDA);
-    expect(proposal.voteCount.toNumber()).to.equal(tokenWeight);
-    console.log(`✅ Very large weight vote recorded: ${proposal.voteCount.toNumber()}`);
+    expect(proposal.yesVotes.toNumber()).to.equal(vote.tokenWeight.toNumber());
+    console.log(`✅ Bob voted with ${vote.tokenWeight} (${vote.delegatedWeight} delegated by Erin) despite a pre-funded receipt`);
   });
 
-  it("Accumulated votes from multiple users with various weights", async () => {
+  it("Delegator cannot vote again after their delegate used their weight", async () => {
     const count = await program.account.userProfile
       .fetch(userProfilePDA)
-      .then((userProfile) => userProfile.proposalCount.toNumber());
+      .then((userProfile) => userProfile.proposalCount.toNumber() - 1);
 
+    try {
+      await castVote(user, count, erin);
+      expect.fail("Expected transaction to fail but it succeeded");
+    } catch (error) {
+      expect(error.toString()).to.include("DelegationAlreadyUsed");
+      console.log("✅ Double counting prevented by the delegation receipt");
+    }
+  });
+
+  it("Delegate withdrawing frees the delegator to vote directly", async () => {
+    const count = await program.account.userProfile
+      .fetch(userProfilePDA)
+      .then((userProfile) => userProfile.proposalCount.toNumber() - 1);
     const proposalPDA = generateProposalPDA(user, program.programId, count);
+    const receiptPDA = generateDelegationReceiptPDA(erin.publicKey, user, program.programId, count);
+    const withdrawAccounts = {
+      voteAccount: generateVotePDA(bob.publicKey, user, program.programId, count),
+      user: bob.publicKey,
+      proposal: proposalPDA,
+      voiceCredits: null,
+      fund: fundPDA,
+    };
+
+    // Every receipt written with the vote has to be handed back
+    try {
+      await program.methods
+        .withdrawVote(new BN(count), user)
+        .accounts(withdrawAccounts)
+        .signers([bob])
+        .rpc();
+      expect.fail("Expected transaction to fail but it succeeded");
+    } catch (error) {
+      expect(error.toString()).to.include("InvalidDelegationAccounts");
+    }
+
     await program.methods
-      .createProposal("Accumulation Test", "Testing vote accumulation", new anchor.BN(100000000))
-      .accounts({
-        proposal: proposalPDA,
-        userProfile: userProfilePDA,
-        user: user,
-      })
+      .withdrawVote(new BN(count), user)
+      .accounts(withdrawAccounts)
+      .remainingAccounts([{ pubkey: receiptPDA, isSigner: false, isWritable: true }])
+      .signers([bob])
       .rpc();
+    expect(await provider.connection.getAccountInfo(receiptPDA)).to.be.null;
 
-    // User votes with weight 100
-    const userVotePDA = generateVotePDA(user, user, program.programId, count);
+    const erinVotePDA = await castVote(user, count, erin, { no: {} });
+    const erinVote = await program.account.voteAccount.fetch(erinVotePDA);
+    const proposal = await program.account.proposal.fetch(proposalPDA);
+    expect(proposal.yesVotes.toNumber()).to.equal(0);
+    expect(proposal.noVotes.toNumber()).to.equal(erinVote.tokenWeight.toNumber());
+    console.log("✅ Bob withdrew, his receipts closed and Erin voted herself");
+  });
+
+  it("Topic-scoped delegation only covers its category", async () => {
+    const GRANTS_CATEGORY = 1;
+    const frank = await createLockedVoter(5_000_000, MAX_LOCK_DURATION);
+    await program.methods
+      .delegateVotes(bob.publicKey, GRANTS_CATEGORY)
+      .accounts({ delegator: frank.publicKey, fund: fundPDA })
+      .signers([frank])
+      .rpc();
+
+    // Proposal in a different category
+    const count = await program.account.userProfile
+      .fetch(userProfilePDA)
+      .then((userProfile) => userProfile.proposalCount.toNumber());
     await program.methods
-      .voteOnProposal(new BN(count), user, new BN(100))
+      .createProposal("Category Test", "Not a grants proposal", new anchor.BN(100000000), 2, [], null)
       .accounts({
-        voteAccount: userVotePDA,
+        proposal: generateProposalPDA(user, program.programId, count),
+        userProfile: userProfilePDA,
         user: user,
-        proposal: proposalPDA,
+        round: roundPDA,
+        fund: fundPDA,
       })
       .rpc();
 
-    // Bob votes with weight 250
-    const bobVotePDA = generateVotePDA(bob.publicKey, user, program.programId, count);
+    try {
+      await castVote(user, count, bob, { yes: {} }, [
+        { key: frank.publicKey, category: GRANTS_CATEGORY },
+      ]);
+      expect.fail("Expected transaction to fail but it succeeded");
+    } catch (error) {
+      expect(error.toString()).to.include("DelegationCategoryMismatch");
+      console.log("✅ Grants-only delegation not usable on other categories");
+    }
+  });
+
+  it("Delegator can revoke their delegation", async () => {
+    const delegationPDA = generateDelegationPDA(erin.publicKey, ALL_CATEGORIES, program.programId);
+
     await program.methods
-      .voteOnProposal(new BN(count), user, new BN(250))
-      .accounts({
-        voteAccount: bobVotePDA,
-        user: bob.publicKey,
-        proposal: proposalPDA,
-      })
-      .signers([bob])
+      .revokeDelegation(ALL_CATEGORIES)
+      .accounts({ delegator: erin.publicKey, fund: fundPDA })
+      .signers([erin])
       .rpc();
 
-    // Alice votes with weight 500
-    const aliceVotePDA = generateVotePDA(alice.publicKey, user, program.programId, count);
+    const closed = await provider.connection.getAccountInfo(delegationPDA);
+    expect(closed).to.be.null;
+    console.log("✅ Erin revoked her delegation");
+  });
+
+  // ==================== QUADRATIC VOTING TESTS ====================
+
+  it("Admin grants voice credits", async () => {
     await program.methods
-      .voteOnProposal(new BN(count), user, new BN(500))
-      .accounts({
-        voteAccount: aliceVotePDA,
-        user: alice.publicKey,
-        proposal: proposalPDA,
-      })
-      .signers([alice])
+      .grantVoiceCredits(bob.publicKey, new BN(100))
+      .accounts({ admin: user, fund: fundPDA })
       .rpc();
 
-    const proposal = await program.account.proposal.fetch(proposalPDA);
-    expect(proposal.voteCount.toNumber()).to.equal(850); // 100 + 250 + 500
-    console.log(`✅ Accumulated votes: 100 + 250 + 500 = ${proposal.voteCount.toNumber()}`);
+    const credits = await program.account.voiceCredits.fetch(
+      generateVoiceCreditsPDA(bob.publicKey, program.programId)
+    );
+    expect(credits.balance.toNumber()).to.equal(100);
+    console.log("✅ Bob received 100 voice credits");
+  });
+
+  it("Non-admin cannot grant voice credits", async () => {
+    try {
+      await program.methods
+        .grantVoiceCredits(bob.publicKey, new BN(1_000_000))
+        .accounts({ admin: bob.publicKey, fund: fundPDA })
+        .signers([bob])
+        .rpc();
+      expect.fail("Expected transaction to fail but it succeeded");
+    } catch (error) {
+      expect(error.toString()).to.include("Unauthorized");
+      console.log("✅ Non-admin correctly prevented from granting credits");
+    }
+  });
+
+  it("Admin cannot grant voice credits to themselves", async () => {
+    try {
+      await program.methods
+        .grantVoiceCredits(user, new BN(100))
+        .accounts({ admin: user, fund: fundPDA })
+        .rpc();
+      expect.fail("Expected transaction to fail but it succeeded");
+    } catch (error) {
+      expect(error.toString()).to.include("Unauthorized");
+      console.log("✅ Self-grant of voice credits refused");
+    }
   });
 
-  // ==================== VOTING TIME WINDOW TESTS ====================
+  it("Voice credit grants are capped per voter", async () => {
+    try {
+      // Bob already holds 100 credits
+      await program.methods
+        .grantVoiceCredits(bob.publicKey, new BN(901))
+        .accounts({ admin: user, fund: fundPDA })
+        .rpc();
+      expect.fail("Expected transaction to fail but it succeeded");
+    } catch (error) {
+      expect(error.toString()).to.include("VoiceCreditCapExceeded");
+    }
+
+    const credits = await program.account.voiceCredits.fetch(
+      generateVoiceCreditsPDA(bob.publicKey, program.programId)
+    );
+    expect(credits.balance.toNumber()).to.equal(100);
+    console.log("✅ Grant past the voice credit cap refused");
+  });
+
+  it("Quadratic mode is set by the round, not the proposer", async () => {
+    await createRound(QUADRATIC_ROUND_ID, null, QUADRATIC);
+
+    const round = await program.account.round.fetch(quadraticRoundPDA);
+    expect(round.votingMode).to.deep.equal(QUADRATIC);
 
-  it("Verify vote account stores all required data", async () => {
     const count = await program.account.userProfile
       .fetch(userProfilePDA)
       .then((userProfile) => userProfile.proposalCount.toNumber());
+    const proposalPDA = generateProposalPDA(user, program.programId, count - 1);
+    const proposal = await program.account.proposal.fetch(proposalPDA);
+    expect(proposal.votingMode).to.deep.equal(TOKEN_WEIGHTED);
+    console.log("✅ Round carries the voting mode for its proposals");
+  });
 
+  it("Quadratic vote charges n² credits for n votes", async () => {
+    const count = await program.account.userProfile
+      .fetch(userProfilePDA)
+      .then((userProfile) => userProfile.proposalCount.toNumber());
     const proposalPDA = generateProposalPDA(user, program.programId, count);
     await program.methods
-      .createProposal("Data Structure Test", "Verify vote data", new anchor.BN(100000000))
+      .createProposal("Quadratic Test", "Community grant", new anchor.BN(100000000), GENERAL_CATEGORY, [], null)
       .accounts({
         proposal: proposalPDA,
         userProfile: userProfilePDA,
         user: user,
+        round: quadraticRoundPDA,
+        fund: fundPDA,
       })
       .rpc();
 
-    const votePDA = generateVotePDA(user, user, program.programId, count);
-    const tokenWeight = 333;
+    const votePDA = await castVote(user, count, bob, { yes: {} }, [], new BN(7));
 
-    await program.methods
-      .voteOnProposal(new BN(count), user, new BN(tokenWeight))
-      .accounts({
-        voteAccount: votePDA,
-        user: user,
-        proposal: proposalPDA,
-      })
-      .rpc();
+    const vote = await program.account.voteAccount.fetch(votePDA);
+    expect(vote.tokenWeight.toNumber()).to.equal(7);
+    expect(vote.creditsSpent.toNumber()).to.equal(49);
 
-    const voteAccount = await program.account.voteAccount.fetch(votePDA);
-    
-    // Verify all fields are properly stored
-    expect(voteAccount.timestamp).to.exist;
-    expect(voteAccount.tokenWeight).to.exist;
-    expect(voteAccount.bump).to.exist;
-    expect(voteAccount.timestamp.toNumber()).to.be.greaterThan(0);
-    expect(voteAccount.tokenWeight.toNumber()).to.equal(tokenWeight);
-    expect(voteAccount.bump).to.be.greaterThan(0);
-    
-    console.log(`✅ Vote account complete: timestamp=${voteAccount.timestamp}, weight=${voteAccount.tokenWeight}, bump=${voteAccount.bump}`);
+    const credits = await program.account.voiceCredits.fetch(
+      generateVoiceCreditsPDA(bob.publicKey, program.programId)
+    );
+    expect(credits.balance.toNumber()).to.equal(51);
+
+    const proposal = await program.account.proposal.fetch(proposalPDA);
+    expect(proposal.votingMode).to.deep.equal(QUADRATIC);
+    expect(proposal.yesVotes.toNumber()).to.equal(7);
+    console.log("✅ 7 quadratic votes cost Bob 49 credits");
   });
 
-  it("Different users can have different weights on the same proposal", async () => {
+  it("Cannot buy more quadratic votes than credits allow", async () => {
     const count = await program.account.userProfile
       .fetch(userProfilePDA)
-      .then((userProfile) => userProfile.proposalCount.toNumber());
+      .then((userProfile) => userProfile.proposalCount.toNumber() - 1);
 
-    const proposalPDA = generateProposalPDA(user, program.programId, count);
     await program.methods
-      .createProposal("Weight Variety Test", "Different weights from different users", new anchor.BN(100000000))
-      .accounts({
-        proposal: proposalPDA,
-        userProfile: userProfilePDA,
-        user: user,
-      })
+      .grantVoiceCredits(alice.publicKey, new BN(10))
+      .accounts({ admin: user, fund: fundPDA })
       .rpc();
 
-    // User votes with weight 10
-    const userVotePDA = generateVotePDA(user, user, program.programId, count);
+    try {
+      // 4 votes cost 16 credits
+      await castVote(user, count, alice, { yes: {} }, [], new BN(4));
+      expect.fail("Expected transaction to fail but it succeeded");
+    } catch (error) {
+      expect(error.toString()).to.include("InsufficientCredits");
+      console.log("✅ Quadratic cost enforced against credit balance");
+    }
+  });
+
+  it("Withdrawing a quadratic vote refunds its credits", async () => {
+    const count = await program.account.userProfile
+      .fetch(userProfilePDA)
+      .then((userProfile) => userProfile.proposalCount.toNumber() - 1);
+    const creditsPDA = generateVoiceCreditsPDA(bob.publicKey, program.programId);
+
     await program.methods
-      .voteOnProposal(new BN(count), user, new BN(10))
+      .withdrawVote(new BN(count), user)
       .accounts({
-        voteAccount: userVotePDA,
-        user: user,
-        proposal: proposalPDA,
+        voteAccount: generateVotePDA(bob.publicKey, user, program.programId, count),
+        user: bob.publicKey,
+        proposal: generateProposalPDA(user, program.programId, count),
+        voiceCredits: creditsPDA,
+        fund: fundPDA,
       })
+      .signers([bob])
       .rpc();
 
-    // Bob votes with weight 20
-    const bobVotePDA = generateVotePDA(bob.publicKey, user, program.programId, count);
+    const credits = await program.account.voiceCredits.fetch(creditsPDA);
+    expect(credits.balance.toNumber()).to.equal(100);
+    console.log("✅ Bob's 49 credits were refunded");
+  });
+
+  // ==================== MULTIPLE USERS TESTS ====================
+
+  it("Multiple users can create proposals independently", async () => {
+    // Initialize Alice
+    const aliceProfilePDA = generatePDA(alice.publicKey, program.programId);
+
     await program.methods
-      .voteOnProposal(new BN(count), user, new BN(20))
+      .initializeUser()
       .accounts({
-        voteAccount: bobVotePDA,
-        user: bob.publicKey,
-        proposal: proposalPDA,
+        userProfile: aliceProfilePDA,
+        user: alice.publicKey,
+        fund: fundPDA,
       })
-      .signers([bob])
+      .signers([alice])
       .rpc();
 
-    // Alice votes with weight 30
-    const aliceVotePDA = generateVotePDA(alice.publicKey, user, program.programId, count);
+    // Alice creates a proposal
+    const aliceProposalPDA = generateProposalPDA(alice.publicKey, program.programId, 0);
     await program.methods
-      .voteOnProposal(new BN(count), user, new BN(30))
+      .createProposal("Alice's Proposal", "Alice's idea", new anchor.BN(300000000), GENERAL_CATEGORY, [], null)
       .accounts({
-        voteAccount: aliceVotePDA,
+        proposal: aliceProposalPDA,
+        userProfile: aliceProfilePDA,
         user: alice.publicKey,
-        proposal: proposalPDA,
+        round: roundPDA,
+        fund: fundPDA,
       })
       .signers([alice])
       .rpc();
 
-    // Verify each vote has the correct weight stored
-    const userVote = await program.account.voteAccount.fetch(userVotePDA);
-    const bobVote = await program.account.voteAccount.fetch(bobVotePDA);
-    const aliceVote = await program.account.voteAccount.fetch(aliceVotePDA);
+    const aliceProposal = await program.account.proposal.fetch(aliceProposalPDA);
+    expect(aliceProposal.owner.toString()).to.equal(alice.publicKey.toString());
+    expect(aliceProposal.title).to.equal("Alice's Proposal");
 
-    expect(userVote.tokenWeight.toNumber()).to.equal(10);
-    expect(bobVote.tokenWeight.toNumber()).to.equal(20);
-    expect(aliceVote.tokenWeight.toNumber()).to.equal(30);
+    const aliceProfile = await program.account.userProfile.fetch(aliceProfilePDA);
+    expect(aliceProfile.proposalCount.toNumber()).to.equal(1);
 
-    const proposal = await program.account.proposal.fetch(proposalPDA);
-    expect(proposal.voteCount.toNumber()).to.equal(60); // 10 + 20 + 30
-    
-    console.log(`✅ Individual weights verified: User(10), Bob(20), Alice(30) = Total(${proposal.voteCount.toNumber()})`);
+    console.log("✅ Multiple users can create proposals independently");
   });
 
-  it("Proposal vote count starts at zero and accumulates correctly", async () => {
+  it("Everyone votes on Alice's proposal, testing vote accumulation", async () => {
+    const aliceProposalPDA = generateProposalPDA(alice.publicKey, program.programId, 0);
+
+    let expected = 0;
+    for (const voter of [alice, undefined, bob]) {
+      const votePDA = await castVote(alice.publicKey, 0, voter);
+      const voteAccount = await program.account.voteAccount.fetch(votePDA);
+      expected += voteAccount.tokenWeight.toNumber();
+    }
+
+    const proposal = await program.account.proposal.fetch(aliceProposalPDA);
+    expect(proposal.yesVotes.toNumber()).to.equal(expected);
+    console.log(`✅ Multiple users voted on Alice's proposal, total votes: ${proposal.yesVotes.toNumber()}`);
+  });
+
+  it("Vote timestamp and weight are correctly recorded", async () => {
+    // Create a new proposal for this test
     const count = await program.account.userProfile
       .fetch(userProfilePDA)
       .then((userProfile) => userProfile.proposalCount.toNumber());
 
     const proposalPDA = generateProposalPDA(user, program.programId, count);
     await program.methods
-      .createProposal("Count Tracking Test", "Verify count starts at zero", new anchor.BN(100000000))
+      .createProposal("Timestamp Test", "Testing vote timestamps", new anchor.BN(100000000), GENERAL_CATEGORY, [], null)
       .accounts({
         proposal: proposalPDA,
         userProfile: userProfilePDA,
         user: user,
+        round: roundPDA,
+        fund: fundPDA,
       })
       .rpc();
 
-    // Verify initial count is 0
-    let proposal = await program.account.proposal.fetch(proposalPDA);
-    expect(proposal.voteCount.toNumber()).to.equal(0);
-    console.log(`   Initial vote count: ${proposal.voteCount.toNumber()}`);
+    const votePDA = await castVote(user, count);
+    const voteAccount = await program.account.voteAccount.fetch(votePDA);
 
-    // Add first vote with weight 17
-    const userVotePDA = generateVotePDA(user, user, program.programId, count);
-    await program.methods
-      .voteOnProposal(new BN(count), user, new BN(17))
-      .accounts({
-        voteAccount: userVotePDA,
-        user: user,
-        proposal: proposalPDA,
-      })
-      .rpc();
+    // Verify timestamp exists and is a reasonable Unix timestamp (after 2020)
+    expect(voteAccount.timestamp.toNumber()).to.be.greaterThan(1577836800); // Jan 1, 2020
+    expect(voteAccount.timestamp.toNumber()).to.be.lessThan(2147483647); // Max 32-bit timestamp
+    expect(voteAccount.tokenWeight.toNumber()).to.be.greaterThan(0);
+    expect(voteAccount.bump).to.be.greaterThan(0);
+    console.log(`✅ Vote timestamp: ${voteAccount.timestamp.toNumber()}, weight: ${voteAccount.tokenWeight.toNumber()}`);
+  });
 
-    proposal = await program.account.proposal.fetch(proposalPDA);
-    expect(proposal.voteCount.toNumber()).to.equal(17);
-    console.log(`   After first vote (17): ${proposal.voteCount.toNumber()}`);
+  it("Proposal vote count starts at zero and accumulates correctly", async () => {
+    const count = await program.account.userProfile
+      .fetch(userProfilePDA)
+      .then((userProfile) => userProfile.proposalCount.toNumber());
 
-    // Add second vote with weight 23
-    const bobVotePDA = generateVotePDA(bob.publicKey, user, program.programId, count);
+    const proposalPDA = generateProposalPDA(user, program.programId, count);
     await program.methods
-      .voteOnProposal(new BN(count), user, new BN(23))
+      .createProposal("Count Tracking Test", "Verify count starts at zero", new anchor.BN(100000000), GENERAL_CATEGORY, [], null)
       .accounts({
-        voteAccount: bobVotePDA,
-        user: bob.publicKey,
         proposal: proposalPDA,
+        userProfile: userProfilePDA,
+        user: user,
+        round: roundPDA,
+        fund: fundPDA,
       })
-      .signers([bob])
       .rpc();
 
-    proposal = await program.account.proposal.fetch(proposalPDA);
-    expect(proposal.voteCount.toNumber()).to.equal(40); // 17 + 23
-    console.log(`   After second vote (23): ${proposal.voteCount.toNumber()}`);
+    // Verify initial count is 0
+    let proposal = await program.account.proposal.fetch(proposalPDA);
+    expect(proposal.yesVotes.toNumber()).to.equal(0);
+    console.log(`   Initial vote count: ${proposal.yesVotes.toNumber()}`);
+
+    let expected = 0;
+    for (const voter of [undefined, bob, alice]) {
+      const votePDA = await castVote(user, count, voter);
+      const voteAccount = await program.account.voteAccount.fetch(votePDA);
+
+      expected += voteAccount.tokenWeight.toNumber();
+      proposal = await program.account.proposal.fetch(proposalPDA);
+      expect(proposal.yesVotes.toNumber()).to.equal(expected);
+      console.log(`   After vote (${voteAccount.tokenWeight}): ${proposal.yesVotes.toNumber()}`);
+    }
 
-    console.log(`✅ Vote count accumulation verified: 0 → 17 → 40`);
+    console.log(`✅ Vote count accumulation verified: 0 → ${expected}`);
   });
 
   // ==================== VAULT TESTS ====================
 
-  it("Initialize vault", async () => {
-    const vaultPDA = generateVaultPDA(program.programId);
-
-    await program.methods
-      .initializeVault()
-      .accounts({
-        vault: vaultPDA,
-        admin: user,
-      })
-      .rpc();
-
-    const vault = await program.account.vault.fetch(vaultPDA);
-    expect(vault.totalDeposited.toNumber()).to.equal(0);
-    expect(vault.totalClaimed.toNumber()).to.equal(0);
-    console.log("✅ Vault initialized with totalDeposited = 0, totalClaimed = 0");
-  });
-
-  it("Cannot double initialize vault", async () => {
-    const vaultPDA = generateVaultPDA(program.programId);
-
-    try {
-      await program.methods
-        .initializeVault()
-        .accounts({
-          vault: vaultPDA,
-          admin: user,
-        })
-        .rpc();
-      expect.fail("Expected transaction to fail but it succeeded");
-    } catch (error) {
-      expect(error.message).to.include("already in use");
-      console.log("✅ Correctly prevented double vault initialization");
-    }
-  });
-
   it("Deposit SOL to vault", async () => {
-    const vaultPDA = generateVaultPDA(program.programId);
+    const vaultPDA = generateVaultPDA(ROUND_ID, program.programId);
     const depositAmount = 5 * anchor.web3.LAMPORTS_PER_SOL; // 5 SOL
 
     // Get vault balance before deposit
@@ -1398,10 +2526,11 @@ describe("community-fund", () => {
     const totalDepositedBefore = vaultBefore.totalDeposited.toNumber();
 
     await program.methods
-      .depositToVault(new BN(depositAmount))
+      .depositToVault(new BN(ROUND_ID), new BN(depositAmount), "Kickoff donation")
       .accounts({
         vault: vaultPDA,
         depositor: user,
+        fund: fundPDA,
       })
       .rpc();
 
@@ -1409,13 +2538,22 @@ describe("community-fund", () => {
     expect(vaultAfter.totalDeposited.toNumber()).to.equal(
       totalDepositedBefore + depositAmount
     );
+
+    const record = await program.account.depositorRecord.fetch(
+      generateDepositorRecordPDA(vaultPDA, user, program.programId)
+    );
+    expect(record.depositor.toString()).to.equal(user.toString());
+    expect(record.totalContributed.toNumber()).to.equal(depositAmount);
+    expect(record.depositCount.toNumber()).to.equal(1);
+    expect(record.firstDepositAt.toNumber()).to.equal(record.lastDepositAt.toNumber());
+    expect(record.lastMemo).to.equal("Kickoff donation");
     console.log(
       `✅ Deposited ${depositAmount / anchor.web3.LAMPORTS_PER_SOL} SOL to vault`
     );
   });
 
   it("Multiple deposits accumulate correctly", async () => {
-    const vaultPDA = generateVaultPDA(program.programId);
+    const vaultPDA = generateVaultPDA(ROUND_ID, program.programId);
     const deposit1 = 2 * anchor.web3.LAMPORTS_PER_SOL; // 2 SOL
     const deposit2 = 3 * anchor.web3.LAMPORTS_PER_SOL; // 3 SOL
 
@@ -1424,19 +2562,21 @@ describe("community-fund", () => {
 
     // First deposit
     await program.methods
-      .depositToVault(new BN(deposit1))
+      .depositToVault(new BN(ROUND_ID), new BN(deposit1), null)
       .accounts({
         vault: vaultPDA,
         depositor: user,
+        fund: fundPDA,
       })
       .rpc();
 
     // Second deposit
     await program.methods
-      .depositToVault(new BN(deposit2))
+      .depositToVault(new BN(ROUND_ID), new BN(deposit2), "Top-up")
       .accounts({
         vault: vaultPDA,
         depositor: user,
+        fund: fundPDA,
       })
       .rpc();
 
@@ -1444,6 +2584,16 @@ describe("community-fund", () => {
     expect(vaultAfter.totalDeposited.toNumber()).to.equal(
       totalDepositedBefore + deposit1 + deposit2
     );
+
+    // The depositor's record keeps a running total across deposits
+    const record = await program.account.depositorRecord.fetch(
+      generateDepositorRecordPDA(vaultPDA, user, program.programId)
+    );
+    expect(record.depositCount.toNumber()).to.equal(3);
+    expect(record.totalContributed.toNumber()).to.equal(
+      5 * anchor.web3.LAMPORTS_PER_SOL + deposit1 + deposit2
+    );
+    expect(record.lastMemo).to.equal("Top-up");
     console.log(
       `✅ Multiple deposits accumulated: ${
         (deposit1 + deposit2) / anchor.web3.LAMPORTS_PER_SOL
@@ -1452,17 +2602,18 @@ describe("community-fund", () => {
   });
 
   it("Bob can also deposit to vault", async () => {
-    const vaultPDA = generateVaultPDA(program.programId);
+    const vaultPDA = generateVaultPDA(ROUND_ID, program.programId);
     const depositAmount = 1 * anchor.web3.LAMPORTS_PER_SOL; // 1 SOL
 
     const vaultBefore = await program.account.vault.fetch(vaultPDA);
     const totalDepositedBefore = vaultBefore.totalDeposited.toNumber();
 
     await program.methods
-      .depositToVault(new BN(depositAmount))
+      .depositToVault(new BN(ROUND_ID), new BN(depositAmount), null)
       .accounts({
         vault: vaultPDA,
         depositor: bob.publicKey,
+        fund: fundPDA,
       })
       .signers([bob])
       .rpc();
@@ -1471,9 +2622,431 @@ describe("community-fund", () => {
     expect(vaultAfter.totalDeposited.toNumber()).to.equal(
       totalDepositedBefore + depositAmount
     );
+
+    // Each depositor gets their own record
+    const record = await program.account.depositorRecord.fetch(
+      generateDepositorRecordPDA(vaultPDA, bob.publicKey, program.programId)
+    );
+    expect(record.totalContributed.toNumber()).to.equal(depositAmount);
+    expect(record.depositCount.toNumber()).to.equal(1);
+    expect(record.lastMemo).to.equal("");
     console.log("✅ Bob deposited 1 SOL to vault");
   });
 
+  it("Spendable balance leaves the vault's rent-exempt reserve", async () => {
+    const vaultPDA = generateVaultPDA(ROUND_ID, program.programId);
+    const vaultInfo = await provider.connection.getAccountInfo(vaultPDA);
+    const reserve = await provider.connection.getMinimumBalanceForRentExemption(vaultInfo.data.length);
+
+    const spendable = await program.methods
+      .getSpendableBalance(new BN(ROUND_ID))
+      .accounts({ vault: vaultPDA, fund: fundPDA })
+      .view();
+
+    expect(spendable.toNumber()).to.equal(vaultInfo.lamports - reserve);
+    console.log(`✅ Spendable: ${spendable.toNumber()} of ${vaultInfo.lamports} lamports`);
+  });
+
+  it("Reconcile books direct transfers as external donations", async () => {
+    const vaultPDA = generateVaultPDA(ROUND_ID, program.programId);
+    const vaultBefore = await program.account.vault.fetch(vaultPDA);
+
+    // A plain SOL transfer bypasses deposit_to_vault
+    const donation = anchor.web3.LAMPORTS_PER_SOL / 2;
+    await provider.sendAndConfirm(
+      new Transaction().add(
+        SystemProgram.transfer({ fromPubkey: user, toPubkey: vaultPDA, lamports: donation })
+      )
+    );
+
+    const signature = await program.methods
+      .reconcileVault(new BN(ROUND_ID))
+      .accounts({ vault: vaultPDA, fund: fundPDA })
+      .rpc({ commitment: "confirmed" });
+
+    // Read the event from the transaction's logs rather than racing a listener
+    const tx = await provider.connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const events = [
+      ...new anchor.EventParser(program.programId, program.coder).parseLogs(tx.meta.logMessages),
+    ];
+    const reconciled = events.find((event) => event.name === "vaultReconciled");
+    expect(reconciled, "VaultReconciled event").to.not.be.undefined;
+
+    const vaultAfter = await program.account.vault.fetch(vaultPDA);
+    expect(vaultAfter.externalDonations.toNumber()).to.equal(
+      vaultBefore.externalDonations.toNumber() + donation
+    );
+    expect(vaultAfter.totalDeposited.toNumber()).to.equal(vaultBefore.totalDeposited.toNumber());
+    expect(reconciled.data.vault.toString()).to.equal(vaultPDA.toString());
+    expect(reconciled.data.newDonations.toNumber()).to.equal(donation);
+    expect(reconciled.data.shortfall.toNumber()).to.equal(0);
+
+    // Running it again finds nothing new
+    await program.methods
+      .reconcileVault(new BN(ROUND_ID))
+      .accounts({ vault: vaultPDA, fund: fundPDA })
+      .rpc();
+    const vaultFinal = await program.account.vault.fetch(vaultPDA);
+    expect(vaultFinal.externalDonations.toNumber()).to.equal(vaultAfter.externalDonations.toNumber());
+    console.log(`✅ Reconciled ${donation} lamports of external donations`);
+  });
+
+  it("Deposit memo longer than 64 bytes is rejected", async () => {
+    const vaultPDA = generateVaultPDA(ROUND_ID, program.programId);
+    try {
+      await program.methods
+        .depositToVault(new BN(ROUND_ID), new BN(1000), "x".repeat(65))
+        .accounts({
+          vault: vaultPDA,
+          depositor: user,
+          fund: fundPDA,
+        })
+        .rpc();
+      expect.fail("Expected transaction to fail but it succeeded");
+    } catch (error) {
+      expect(error.toString()).to.include("MemoTooLong");
+      console.log("✅ Oversized memo correctly rejected");
+    }
+  });
+
+  it("Fresh depositor cannot capture an earlier donation by ragequitting", async () => {
+    const vaultPDA = generateVaultPDA(ROUND_ID, program.programId);
+    const depositor = Keypair.generate();
+    const airdropSig = await provider.connection.requestAirdrop(
+      depositor.publicKey,
+      2 * anchor.web3.LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(airdropSig, "confirmed");
+
+    // The vault already holds the donation reconciled above
+    const vaultBeforeDeposit = await program.account.vault.fetch(vaultPDA);
+    expect(vaultBeforeDeposit.externalDonations.toNumber()).to.be.greaterThan(0);
+
+    const depositAmount = anchor.web3.LAMPORTS_PER_SOL;
+    await program.methods
+      .depositToVault(new BN(ROUND_ID), new BN(depositAmount), null)
+      .accounts({
+        vault: vaultPDA,
+        depositor: depositor.publicKey,
+        fund: fundPDA,
+      })
+      .signers([depositor])
+      .rpc();
+
+    // Payout = uncommitted balance above rent * depositor's shares / all shares
+    const recordPDA = generateDepositorRecordPDA(vaultPDA, depositor.publicKey, program.programId);
+    const record = await program.account.depositorRecord.fetch(recordPDA);
+    const vaultBefore = await program.account.vault.fetch(vaultPDA);
+    const vaultInfo = await provider.connection.getAccountInfo(vaultPDA);
+    const reserve = await provider.connection.getMinimumBalanceForRentExemption(vaultInfo.data.length);
+    const available = BigInt(
+      Math.max(vaultInfo.lamports - reserve - vaultBefore.committed.toNumber(), 0)
+    );
+    const expected = Number(
+      (available * BigInt(record.shares.toString())) / BigInt(vaultBefore.totalShares.toString())
+    );
+
+    await program.methods
+      .ragequit(new BN(ROUND_ID))
+      .accounts({
+        vault: vaultPDA,
+        depositor: depositor.publicKey,
+        fund: fundPDA,
+        config: generateConfigPDA(program.programId),
+      })
+      .signers([depositor])
+      .rpc();
+
+    const vaultAfter = await program.account.vault.fetch(vaultPDA);
+    expect(vaultAfter.totalBurned.toNumber()).to.equal(
+      vaultBefore.totalBurned.toNumber() + depositAmount
+    );
+    expect(vaultAfter.totalShares.toString()).to.equal(
+      vaultBefore.totalShares.sub(record.shares).toString()
+    );
+    expect(vaultAfter.totalWithdrawn.toNumber()).to.equal(
+      vaultBefore.totalWithdrawn.toNumber() + expected
+    );
+    const lamportsAfter = await provider.connection.getBalance(vaultPDA);
+    expect(lamportsAfter).to.equal(vaultInfo.lamports - expected);
+
+    // Shares were bought at the current price, so none of the donation leaves with them
+    expect(expected).to.be.at.most(depositAmount);
+    expect(expected).to.be.at.least(depositAmount - 1);
+
+    // The contribution record is burned
+    expect(await provider.connection.getAccountInfo(recordPDA)).to.be.null;
+    console.log(`✅ Ragequit returned ${expected} lamports for a 1 SOL deposit made after a donation`);
+  });
+
+  it("Cannot ragequit without a contribution", async () => {
+    const vaultPDA = generateVaultPDA(ROUND_ID, program.programId);
+    try {
+      await program.methods
+        .ragequit(new BN(ROUND_ID))
+        .accounts({
+          vault: vaultPDA,
+          depositor: alice.publicKey,
+          fund: fundPDA,
+          config: generateConfigPDA(program.programId),
+        })
+        .signers([alice])
+        .rpc();
+      expect.fail("Expected transaction to fail but it succeeded");
+    } catch (error) {
+      expect(error.toString()).to.include("AccountNotInitialized");
+      console.log("✅ Ragequit without a deposit record correctly rejected");
+    }
+  });
+
+  // ==================== TOKEN TREASURY TESTS ====================
+
+  let treasuryMint: PublicKey;
+  let userTreasuryAccount: PublicKey;
+
+  it("Deposit SPL tokens into a per-mint vault account", async () => {
+    const payer = (provider.wallet as anchor.Wallet).payer;
+    treasuryMint = await createMint(provider.connection, payer, user, null, 6);
+    userTreasuryAccount = (
+      await getOrCreateAssociatedTokenAccount(provider.connection, payer, treasuryMint, user)
+    ).address;
+    await mintTo(provider.connection, payer, treasuryMint, userTreasuryAccount, user, 5_000_000);
+    await createRound(TOKEN_ROUND_ID, treasuryMint);
+
+    const tokenVaultPDA = generateVaultPDA(TOKEN_ROUND_ID, program.programId);
+    const ledgerPDA = generateTokenLedgerPDA(tokenVaultPDA, treasuryMint, program.programId);
+    const vaultTokenPDA = generateVaultTokenPDA(tokenVaultPDA, treasuryMint, program.programId);
+    for (const amount of [2_000_000, 1_000_000]) {
+      await program.methods
+        .depositToken(new BN(TOKEN_ROUND_ID), new BN(amount))
+        .accounts({
+          depositorTokenAccount: userTreasuryAccount,
+          mint: treasuryMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          depositor: user,
+          fund: fundPDA,
+        })
+        .rpc();
+    }
+
+    const ledger = await program.account.tokenLedger.fetch(ledgerPDA);
+    expect(ledger.mint.toString()).to.equal(treasuryMint.toString());
+    expect(ledger.totalDeposited.toNumber()).to.equal(3_000_000);
+    expect(ledger.totalClaimed.toNumber()).to.equal(0);
+    expect(ledger.emergencyWithdrawn.toNumber()).to.equal(0);
+    // Every token grant needs the council until a threshold is set for the mint
+    expect(ledger.multisigThreshold.toNumber()).to.equal(0);
+    // Nothing reserved until a token grant is approved
+    expect(ledger.committed.toNumber()).to.equal(0);
+    const vaultBalance = await provider.connection.getTokenAccountBalance(vaultTokenPDA);
+    expect(vaultBalance.value.amount).to.equal("3000000");
+
+    // SOL accounting is untouched
+    const vault = await program.account.vault.fetch(tokenVaultPDA);
+    expect(vault.totalClaimed.toNumber()).to.equal(0);
+    console.log("✅ 3 tokens deposited, tracked in their own ledger");
+  });
+
+  it("Token grants get a multisig threshold in their own mint", async () => {
+    const tokenVaultPDA = generateVaultPDA(TOKEN_ROUND_ID, program.programId);
+    const ledgerPDA = generateTokenLedgerPDA(tokenVaultPDA, treasuryMint, program.programId);
+
+    // At most a tenth of the 3 tokens deposited
+    try {
+      await program.methods
+        .setTokenMultisigThreshold(new BN(TOKEN_ROUND_ID), treasuryMint, new BN(300_001))
+        .accounts({ vault: tokenVaultPDA, admin: user, fund: fundPDA })
+        .rpc();
+      expect.fail("Expected transaction to fail but it succeeded");
+    } catch (error) {
+      expect(error.toString()).to.include("InvalidGovernanceParams");
+    }
+
+    try {
+      await program.methods
+        .setTokenMultisigThreshold(new BN(TOKEN_ROUND_ID), treasuryMint, new BN(100_000))
+        .accounts({ vault: tokenVaultPDA, admin: bob.publicKey, fund: fundPDA })
+        .signers([bob])
+        .rpc();
+      expect.fail("Expected transaction to fail but it succeeded");
+    } catch (error) {
+      expect(error.toString()).to.include("Unauthorized");
+    }
+
+    await program.methods
+      .setTokenMultisigThreshold(new BN(TOKEN_ROUND_ID), treasuryMint, new BN(300_000))
+      .accounts({ vault: tokenVaultPDA, admin: user, fund: fundPDA })
+      .rpc();
+    const ledger = await program.account.tokenLedger.fetch(ledgerPDA);
+    expect(ledger.multisigThreshold.toNumber()).to.equal(300_000);
+    console.log("✅ Token multisig threshold set in base units of the mint");
+  });
+
+  // Token-2022 mint with a 1% transfer fee
+  const createTransferFeeMint = async () => {
+    const feeMint = Keypair.generate();
+    const mintLen = getMintLen([ExtensionType.TransferFeeConfig]);
+    const lamports = await provider.connection.getMinimumBalanceForRentExemption(mintLen);
+    await provider.sendAndConfirm(
+      new Transaction().add(
+        SystemProgram.createAccount({
+          fromPubkey: user,
+          newAccountPubkey: feeMint.publicKey,
+          space: mintLen,
+          lamports,
+          programId: TOKEN_2022_PROGRAM_ID,
+        }),
+        createInitializeTransferFeeConfigInstruction(
+          feeMint.publicKey,
+          user,
+          user,
+          100,
+          BigInt(1_000_000),
+          TOKEN_2022_PROGRAM_ID
+        ),
+        createInitializeMintInstruction(feeMint.publicKey, 6, user, null, TOKEN_2022_PROGRAM_ID)
+      ),
+      [feeMint]
+    );
+    return feeMint;
+  };
+
+  it("Token-2022 deposits record the amount received after transfer fees", async () => {
+    const payer = (provider.wallet as anchor.Wallet).payer;
+    const feeMint = await createTransferFeeMint();
+    const source = await createAssociatedTokenAccount(
+      provider.connection,
+      payer,
+      feeMint.publicKey,
+      user,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    await mintTo(provider.connection, payer, feeMint.publicKey, source, user, 1_000_000, [], undefined, TOKEN_2022_PROGRAM_ID);
+
+    await program.methods
+      .depositToken(new BN(TOKEN_ROUND_ID), new BN(1_000_000))
+      .accounts({
+        depositorTokenAccount: source,
+        mint: feeMint.publicKey,
+        depositor: user,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        fund: fundPDA,
+      })
+      .rpc();
+
+    const ledger = await program.account.tokenLedger.fetch(
+      generateTokenLedgerPDA(
+        generateVaultPDA(TOKEN_ROUND_ID, program.programId),
+        feeMint.publicKey,
+        program.programId
+      )
+    );
+    expect(ledger.totalDeposited.toNumber()).to.equal(990_000);
+    console.log("✅ Token-2022 deposit of 1.0 recorded as 0.99 received after the 1% fee");
+  });
+
+  it("Tokens locked with a transfer-fee mint can be unlocked", async () => {
+    const payer = (provider.wallet as anchor.Wallet).payer;
+    const feeMint = await createTransferFeeMint();
+    const source = await createAssociatedTokenAccount(
+      provider.connection,
+      payer,
+      feeMint.publicKey,
+      user,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    await mintTo(provider.connection, payer, feeMint.publicKey, source, user, 1_000_000, [], undefined, TOKEN_2022_PROGRAM_ID);
+
+    // Fund whose governance token charges a transfer fee
+    const feeFundId = 3;
+    const feeFundPDA = generateFundPDA(feeFundId, program.programId);
+    await program.methods
+      .initializeAdmin(
+        new BN(feeFundId),
+        [Keypair.generate().publicKey, Keypair.generate().publicKey],
+        [ALL_ROLES, ALL_ROLES],
+        2
+      )
+      .accounts({
+        user: user,
+        programData: generateProgramDataPDA(program.programId),
+        governanceMint: feeMint.publicKey,
+        fund: feeFundPDA,
+      })
+      .rpc();
+
+    const lockAccounts = {
+      user: user,
+      userTokenAccount: source,
+      governanceMint: feeMint.publicKey,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+      fund: feeFundPDA,
+    };
+    await program.methods
+      .lockTokens(new BN(1_000_000), new BN(2))
+      .accounts(lockAccounts)
+      .rpc();
+    const lockPDA = generateVoterLockPDA(user, program.programId, feeFundPDA);
+    const voterLock = await program.account.voterLock.fetch(lockPDA);
+    expect(voterLock.amount.toNumber()).to.equal(990_000);
+
+    // Escrow now holds the fee withheld on the way in; unlock must still close it
+    await new Promise((resolve) => setTimeout(resolve, 3500));
+    await program.methods
+      .unlockTokens()
+      .accounts(lockAccounts)
+      .rpc();
+
+    expect(await provider.connection.getAccountInfo(lockPDA)).to.be.null;
+    expect(
+      await provider.connection.getAccountInfo(generateLockEscrowPDA(user, program.programId, feeFundPDA))
+    ).to.be.null;
+    const balance = await provider.connection.getTokenAccountBalance(source);
+    expect(balance.value.amount).to.equal("980100");
+    console.log("✅ Fee-bearing lock unlocked: withheld fees harvested and escrow closed");
+  });
+
+  it("Cannot claim tokens before the token grant is approved", async () => {
+    const count = await program.account.userProfile
+      .fetch(userProfilePDA)
+      .then((userProfile) => userProfile.proposalCount.toNumber());
+    await program.methods
+      .createProposal("Token Grant", "Paid in treasury tokens", new BN(1_000_000), GENERAL_CATEGORY, [], null)
+      .accounts({ round: tokenRoundPDA, fund: fundPDA })
+      .rpc();
+
+    const proposal = await program.account.proposal.fetch(
+      generateProposalPDA(user, program.programId, count)
+    );
+    // The requested asset comes from the round
+    expect(proposal.requestedMint.toString()).to.equal(treasuryMint.toString());
+    expect(proposal.roundId.toNumber()).to.equal(TOKEN_ROUND_ID);
+
+    try {
+      await program.methods
+        .claimToken(new BN(count))
+        .accounts({
+          owner: user,
+          ownerTokenAccount: userTreasuryAccount,
+          mint: treasuryMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          round: tokenRoundPDA,
+          fund: fundPDA,
+          config: generateConfigPDA(program.programId),
+        })
+        .rpc();
+      expect.fail("Expected transaction to fail but it succeeded");
+    } catch (error) {
+      expect(error.toString()).to.include("NotApproved");
+      console.log("✅ Token claim correctly requires an approved proposal");
+    }
+  });
+
   // ==================== FINALIZE PROPOSAL TESTS ====================
 
   it("Cannot finalize proposal before voting period ends", async () => {
@@ -1487,12 +3060,17 @@ describe("community-fund", () => {
       .createProposal(
         "Finalization Test",
         "Testing finalization logic",
-        new anchor.BN(1 * anchor.web3.LAMPORTS_PER_SOL)
+        new anchor.BN(1 * anchor.web3.LAMPORTS_PER_SOL),
+        GENERAL_CATEGORY,
+        [],
+        null
       )
       .accounts({
         proposal: proposalPDA,
         userProfile: userProfilePDA,
         user: user,
+        round: roundPDA,
+        fund: fundPDA,
       })
       .rpc();
 
@@ -1503,6 +3081,8 @@ describe("community-fund", () => {
         .accounts({
           proposal: proposalPDA,
           caller: user,
+          round: roundPDA,
+          fund: fundPDA,
         })
         .rpc();
       expect.fail("Expected transaction to fail but it succeeded");
@@ -1534,12 +3114,17 @@ describe("community-fund", () => {
       .createProposal(
         "Winner Flow Test",
         "This proposal will receive enough votes and be finalized",
-        new anchor.BN(2 * anchor.web3.LAMPORTS_PER_SOL) // Request 2 SOL
+        new anchor.BN(2 * anchor.web3.LAMPORTS_PER_SOL) // Request 2 SOL,
+        GENERAL_CATEGORY,
+        [],
+        null
       )
       .accounts({
         proposal: proposalPDA,
         userProfile: userProfilePDA,
         user: user,
+        round: roundPDA,
+        fund: fundPDA,
       })
       .rpc();
 
@@ -1557,50 +3142,15 @@ describe("community-fund", () => {
 
     const proposalPDA = generateProposalPDA(user, program.programId, count);
 
-    // User votes with weight 40
-    const userVotePDA = generateVotePDA(user, user, program.programId, count);
-    await program.methods
-      .voteOnProposal(new BN(count), user, new BN(40))
-      .accounts({
-        voteAccount: userVotePDA,
-        user: user,
-        proposal: proposalPDA,
-      })
-      .rpc();
-
-    // Bob votes with weight 30
-    const bobVotePDA = generateVotePDA(bob.publicKey, user, program.programId, count);
-    await program.methods
-      .voteOnProposal(new BN(count), user, new BN(30))
-      .accounts({
-        voteAccount: bobVotePDA,
-        user: bob.publicKey,
-        proposal: proposalPDA,
-      })
-      .signers([bob])
-      .rpc();
-
-    // Alice votes with weight 35
-    const aliceVotePDA = generateVotePDA(
-      alice.publicKey,
-      user,
-      program.programId,
-      count
-    );
-    await program.methods
-      .voteOnProposal(new BN(count), user, new BN(35))
-      .accounts({
-        voteAccount: aliceVotePDA,
-        user: alice.publicKey,
-        proposal: proposalPDA,
-      })
-      .signers([alice])
-      .rpc();
+    // User, Bob and Alice vote with their locked weight
+    for (const voter of [undefined, bob, alice]) {
+      await castVote(user, count, voter);
+    }
 
     const proposal = await program.account.proposal.fetch(proposalPDA);
-    expect(proposal.voteCount.toNumber()).to.equal(105); // 40 + 30 + 35
+    expect(proposal.yesVotes.toNumber()).to.be.at.least(100);
     console.log(
-      `✅ Proposal received ${proposal.voteCount.toNumber()} votes (above 100 threshold)`
+      `✅ Proposal received ${proposal.yesVotes.toNumber()} votes (above 100 threshold)`
     );
   });
 
@@ -1613,7 +3163,7 @@ describe("community-fund", () => {
       .then((userProfile) => userProfile.proposalCount.toNumber() - 1);
 
     const proposalPDA = generateProposalPDA(user, program.programId, count);
-    const vaultPDA = generateVaultPDA(program.programId);
+    const vaultPDA = generateVaultPDA(ROUND_ID, program.programId);
 
     try {
       await program.methods
@@ -1622,6 +3172,9 @@ describe("community-fund", () => {
           proposal: proposalPDA,
           owner: user,
           vault: vaultPDA,
+          round: roundPDA,
+          fund: fundPDA,
+          config: generateConfigPDA(program.programId),
         })
         .rpc();
       expect.fail("Expected transaction to fail but it succeeded");
@@ -1631,39 +3184,210 @@ describe("community-fund", () => {
     }
   });
 
-  it("Simulate finalization and claim flow", async () => {
-    // Note: In real tests with bankrun, you would:
-    // 1. Warp clock forward 7 days
-    // 2. Call finalize_proposal
-    // 3. Verify status changed to Finalized
-    // 4. Call claim_funds
-    // 5. Verify SOL transferred and status changed to Claimed
+  it("Cannot claim vested funds before funding is approved", async () => {
+    try {
+      await program.methods
+        .claimVested(new BN(vestingProposalId))
+        .accounts({ owner: user, round: roundPDA, fund: fundPDA, config: generateConfigPDA(program.programId) })
+        .rpc();
+      expect.fail("Expected transaction to fail but it succeeded");
+    } catch (error) {
+      expect(error.toString()).to.include("NotApproved");
+      console.log("✅ Vested claim correctly requires an approved proposal");
+    }
+  });
 
-    console.log("\n" + "=".repeat(60));
-    console.log("📋 COMPLETE WINNER FLOW (Conceptual)");
-    console.log("=".repeat(60));
-    console.log("1. ✅ Vault initialized");
-    console.log("2. ✅ 11 SOL deposited to vault (User: 10 SOL, Bob: 1 SOL)");
-    console.log("3. ✅ Proposal created requesting 2 SOL");
-    console.log("4. ✅ Proposal received 105 votes (above 100 threshold)");
-    console.log("5. ⏳ Wait 7 days (604800 seconds)");
-    console.log("6. ⏳ Anyone calls finalize_proposal()");
-    console.log("   → Status: Pending → Finalized");
-    console.log("   → finalized_at timestamp set");
-    console.log("7. ⏳ Winner calls claim_funds()");
-    console.log("   → 2 SOL transferred from vault to winner");
-    console.log("   → Status: Finalized → Claimed");
-    console.log("   → vault.total_claimed updated");
-    console.log("=".repeat(60) + "\n");
+  // Rounds whose voting window closes seconds after they open. local-testing builds
+  // accept a voting period of seconds, so the suite can wait it out.
+  const E2E_ROUND_ID = 6;
+  const E2E_TOKEN_ROUND_ID = 7;
 
-    console.log("⚠️  Note: Steps 5-7 require clock manipulation");
-    console.log(
-      "   Use @solana/bankrun or custom test validator for full integration test"
-    );
+  const createShortRound = async (roundId: number, mint: PublicKey | null) => {
+    await program.methods
+      .updateParams({ ...DEFAULT_PARAMS, votingPeriod: new BN(2) })
+      .accounts({ admin: user, fund: fundPDA })
+      .rpc();
+    const now = Math.floor(Date.now() / 1000);
+    await program.methods
+      .createRound(
+        new BN(roundId),
+        mint,
+        TOKEN_WEIGHTED,
+        new BN(ROUND_BUDGET),
+        new BN(now - 60),
+        new BN(now + 8),
+        new BN(now + 30 * 86400)
+      )
+      .accounts({ admin: user, fund: fundPDA })
+      .rpc();
+    // The round fixed its voting_end, the default period applies again to later rounds
+    await program.methods.updateParams(DEFAULT_PARAMS).accounts({ admin: user, fund: fundPDA }).rpc();
+  };
+
+  // Creates a proposal in the round and votes it through with a fresh locked voter
+  const proposeAndPass = async (roundPDA: PublicKey, title: string, amount: number) => {
+    const count = await program.account.userProfile
+      .fetch(userProfilePDA)
+      .then((userProfile) => userProfile.proposalCount.toNumber());
+    await program.methods
+      .createProposal(title, "Voted through end to end", new BN(amount), GENERAL_CATEGORY, [], null)
+      .accounts({ round: roundPDA, fund: fundPDA })
+      .rpc();
+    const voter = await createLockedVoter(20_000_000, MAX_LOCK_DURATION);
+    await castVote(user, count, voter, { yes: {} });
+
+    // Wait out the voting window, with a margin for the validator clock
+    const round = await program.account.round.fetch(roundPDA);
+    const wait = (round.votingEnd.toNumber() + 2) * 1000 - Date.now();
+    if (wait > 0) {
+      await new Promise((resolve) => setTimeout(resolve, wait));
+    }
+
+    await program.methods
+      .finalizeProposal(new BN(count), user)
+      .accounts({
+        proposal: generateProposalPDA(user, program.programId, count),
+        caller: user,
+        round: roundPDA,
+        fund: fundPDA,
+      })
+      .rpc();
+    return count;
+  };
+
+  it("SOL grant is finalized, approved and claimed once voting closes", async () => {
+    const e2eRoundPDA = generateRoundPDA(E2E_ROUND_ID, program.programId);
+    const e2eVaultPDA = generateVaultPDA(E2E_ROUND_ID, program.programId);
+    const grant = anchor.web3.LAMPORTS_PER_SOL / 2;
+
+    await createShortRound(E2E_ROUND_ID, null);
+    await program.methods
+      .depositToVault(new BN(E2E_ROUND_ID), new BN(2 * anchor.web3.LAMPORTS_PER_SOL), null)
+      .accounts({ vault: e2eVaultPDA, depositor: user, fund: fundPDA })
+      .rpc();
+
+    const count = await proposeAndPass(e2eRoundPDA, "End To End", grant);
+    const proposalPDA = generateProposalPDA(user, program.programId, count);
+    let proposal = await program.account.proposal.fetch(proposalPDA);
+    expect(proposal.status).to.deep.equal({ finalized: {} });
+    const round = await program.account.round.fetch(e2eRoundPDA);
+    expect(round.allocated.toNumber()).to.equal(grant);
+
+    // Below the multisig threshold one treasurer approves
+    await program.methods
+      .approveFunding(new BN(count), user)
+      .accounts({
+        admin: user,
+        proposal: proposalPDA,
+        config: generateConfigPDA(program.programId),
+        fund: fundPDA,
+        vault: e2eVaultPDA,
+        ledger: null,
+        vaultTokenAccount: null,
+      })
+      .rpc();
+    let vault = await program.account.vault.fetch(e2eVaultPDA);
+    expect(vault.committed.toNumber()).to.equal(grant);
+
+    const vaultBefore = await provider.connection.getBalance(e2eVaultPDA);
+    const ownerBefore = await provider.connection.getBalance(user);
+    await program.methods
+      .claimFunds(new BN(count))
+      .accounts({
+        proposal: proposalPDA,
+        owner: user,
+        vault: e2eVaultPDA,
+        round: e2eRoundPDA,
+        fund: fundPDA,
+        config: generateConfigPDA(program.programId),
+      })
+      .rpc();
+
+    proposal = await program.account.proposal.fetch(proposalPDA);
+    expect(proposal.status).to.deep.equal({ claimed: {} });
+    expect(await provider.connection.getBalance(e2eVaultPDA)).to.equal(vaultBefore - grant);
+    // The owner pays the transaction fee out of the grant
+    const ownerGain = (await provider.connection.getBalance(user)) - ownerBefore;
+    expect(ownerGain).to.be.within(grant - 10_000, grant);
+
+    vault = await program.account.vault.fetch(e2eVaultPDA);
+    expect(vault.totalClaimed.toNumber()).to.equal(grant);
+    expect(vault.committed.toNumber()).to.equal(0);
+    console.log("✅ SOL grant voted, finalized, approved and claimed");
+  });
+
+  it("Token grant is finalized, approved by the council and claimed once voting closes", async () => {
+    const e2eRoundPDA = generateRoundPDA(E2E_TOKEN_ROUND_ID, program.programId);
+    const e2eVaultPDA = generateVaultPDA(E2E_TOKEN_ROUND_ID, program.programId);
+    const ledgerPDA = generateTokenLedgerPDA(e2eVaultPDA, treasuryMint, program.programId);
+    const vaultTokenPDA = generateVaultTokenPDA(e2eVaultPDA, treasuryMint, program.programId);
+    const grant = 200_000;
+
+    await createShortRound(E2E_TOKEN_ROUND_ID, treasuryMint);
+    await program.methods
+      .depositToken(new BN(E2E_TOKEN_ROUND_ID), new BN(500_000))
+      .accounts({
+        depositorTokenAccount: userTreasuryAccount,
+        mint: treasuryMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        depositor: user,
+        fund: fundPDA,
+      })
+      .rpc();
+
+    const count = await proposeAndPass(e2eRoundPDA, "End To End Tokens", grant);
+    const proposalPDA = generateProposalPDA(user, program.programId, count);
+
+    // No threshold set for the mint in this vault, so the council has to approve
+    for (const admin of [admin2, admin3]) {
+      await program.methods
+        .approveFunding(new BN(count), user)
+        .accounts({
+          admin: admin.publicKey,
+          proposal: proposalPDA,
+          config: generateConfigPDA(program.programId),
+          fund: fundPDA,
+          vault: e2eVaultPDA,
+          ledger: ledgerPDA,
+          vaultTokenAccount: vaultTokenPDA,
+        })
+        .signers([admin])
+        .rpc();
+    }
+    let proposal = await program.account.proposal.fetch(proposalPDA);
+    expect(proposal.status).to.deep.equal({ approved: {} });
+    let ledger = await program.account.tokenLedger.fetch(ledgerPDA);
+    expect(ledger.committed.toNumber()).to.equal(grant);
+
+    const ownerBefore = await provider.connection.getTokenAccountBalance(userTreasuryAccount);
+    await program.methods
+      .claimToken(new BN(count))
+      .accounts({
+        owner: user,
+        ownerTokenAccount: userTreasuryAccount,
+        mint: treasuryMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        round: e2eRoundPDA,
+        fund: fundPDA,
+        config: generateConfigPDA(program.programId),
+      })
+      .rpc();
+
+    proposal = await program.account.proposal.fetch(proposalPDA);
+    expect(proposal.status).to.deep.equal({ claimed: {} });
+    const vaultBalance = await provider.connection.getTokenAccountBalance(vaultTokenPDA);
+    expect(vaultBalance.value.amount).to.equal(String(500_000 - grant));
+    const ownerAfter = await provider.connection.getTokenAccountBalance(userTreasuryAccount);
+    expect(Number(ownerAfter.value.amount) - Number(ownerBefore.value.amount)).to.equal(grant);
+
+    ledger = await program.account.tokenLedger.fetch(ledgerPDA);
+    expect(ledger.totalClaimed.toNumber()).to.equal(grant);
+    expect(ledger.committed.toNumber()).to.equal(0);
+    console.log("✅ Token grant voted, finalized, approved by 2 treasurers and claimed");
   });
 
   it("Verify vault has enough balance for claims", async () => {
-    const vaultPDA = generateVaultPDA(program.programId);
+    const vaultPDA = generateVaultPDA(ROUND_ID, program.programId);
     const vaultAccount = await provider.connection.getAccountInfo(vaultPDA);
 
     const vaultData = await program.account.vault.fetch(vaultPDA);
@@ -1677,11 +3401,37 @@ describe("community-fund", () => {
       `   Total claimed (tracked): ${vaultData.totalClaimed.toNumber() / anchor.web3.LAMPORTS_PER_SOL} SOL`
     );
 
+    // Nothing has been approved yet, so the whole balance is free
+    expect(vaultData.committed.toNumber()).to.equal(0);
+
     // Vault should have enough balance for the 2 SOL claim
     expect(actualBalance).to.be.greaterThan(2 * anchor.web3.LAMPORTS_PER_SOL);
     console.log("✅ Vault has sufficient balance for future claims");
   });
 
+  it("Cannot expire a proposal before the payout deadline", async () => {
+    const proposalPDA = generateProposalPDA(user, program.programId, 0);
+    try {
+      await program.methods
+        .expireProposal(new BN(0), user)
+        .accounts({
+          proposal: proposalPDA,
+          round: roundPDA,
+          vault: roundVaultPDA,
+          ledger: null,
+          fund: fundPDA,
+        })
+        .rpc();
+      expect.fail("Expected transaction to fail but it succeeded");
+    } catch (error) {
+      expect(error.toString()).to.include("PayoutDeadlineNotPassed");
+    }
+
+    const proposal = await program.account.proposal.fetch(proposalPDA);
+    expect(proposal.status).to.deep.equal({ pending: {} });
+    console.log("✅ Expiry correctly waits for the round's payout deadline");
+  });
+
   it("Test finalization edge cases", async () => {
     // Create a proposal that will not meet threshold
     const count = await program.account.userProfile
@@ -1693,32 +3443,27 @@ describe("community-fund", () => {
       .createProposal(
         "Low Vote Test",
         "This proposal will have insufficient votes",
-        new anchor.BN(1 * anchor.web3.LAMPORTS_PER_SOL)
+        new anchor.BN(1 * anchor.web3.LAMPORTS_PER_SOL),
+        GENERAL_CATEGORY,
+        [],
+        null
       )
       .accounts({
         proposal: proposalPDA,
         userProfile: userProfilePDA,
         user: user,
+        round: roundPDA,
+        fund: fundPDA,
       })
       .rpc();
 
-    // Give it only 50 votes (below 100 threshold)
-    const votePDA = generateVotePDA(user, user, program.programId, count);
-    await program.methods
-      .voteOnProposal(new BN(count), user, new BN(50))
-      .accounts({
-        voteAccount: votePDA,
-        user: user,
-        proposal: proposalPDA,
-      })
-      .rpc();
-
+    // No votes cast (below 100 threshold)
     const proposal = await program.account.proposal.fetch(proposalPDA);
-    expect(proposal.voteCount.toNumber()).to.equal(50);
+    expect(proposal.yesVotes.toNumber()).to.equal(0);
     console.log(
-      "✅ Created low-vote proposal with 50 votes (below 100 threshold)"
+      "✅ Created low-vote proposal with 0 votes (below 100 threshold)"
     );
-    console.log("   → Would be rejected when finalized after 7 days");
+    console.log("   → Would be rejected when finalized after voting closes");
   });
 
   it("Verify proposal data integrity for winner flow", async () => {
@@ -1738,17 +3483,271 @@ describe("community-fund", () => {
       2 * anchor.web3.LAMPORTS_PER_SOL
     );
     expect(proposal.status).to.deep.equal({ pending: {} });
-    expect(proposal.voteCount.toNumber()).to.equal(105);
+    expect(proposal.yesVotes.toNumber()).to.be.at.least(100);
     expect(proposal.finalizedAt.toNumber()).to.equal(0);
     expect(proposal.createdAt.toNumber()).to.be.greaterThan(0);
 
     console.log("✅ Winner proposal data integrity verified:");
     console.log(`   Title: ${proposal.title}`);
     console.log(`   Amount: ${proposal.amountRequested.toNumber() / anchor.web3.LAMPORTS_PER_SOL} SOL`);
-    console.log(`   Votes: ${proposal.voteCount.toNumber()}`);
+    console.log(`   Votes: ${proposal.yesVotes.toNumber()}`);
     console.log(`   Status: Pending`);
   });
 
+  // ==================== EMERGENCY WITHDRAWAL TESTS ====================
+
+  const generateEmergencyWithdrawalPDA = (vault: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("emergency_withdrawal"), vault.toBuffer()],
+      program.programId
+    )[0];
+
+  it("Emergency policy must keep at least two admins in the loop", async () => {
+    try {
+      await program.methods
+        .setEmergencyPolicy({ threshold: 1, timelock: new BN(86400) })
+        .accounts({ admin: user, fund: fundPDA })
+        .rpc();
+      expect.fail("Expected transaction to fail but it succeeded");
+    } catch (error) {
+      expect(error.toString()).to.include("InvalidEmergencyPolicy");
+    }
+
+    const config = await program.account.config.fetch(generateConfigPDA(program.programId));
+    expect(config.emergency.threshold).to.equal(2);
+    expect(config.emergency.timelock.toNumber()).to.equal(2 * 86400);
+    console.log("✅ Single-admin emergency policy correctly rejected");
+  });
+
+  it("Emergency withdrawal can't be proposed while the program runs", async () => {
+    try {
+      await program.methods
+        .proposeEmergencyWithdrawal(new BN(ROUND_ID), new BN(anchor.web3.LAMPORTS_PER_SOL), user, null)
+        .accounts({ vault: roundVaultPDA, admin: user, fund: fundPDA })
+        .rpc();
+      expect.fail("Expected transaction to fail but it succeeded");
+    } catch (error) {
+      expect(error.toString()).to.include("ProgramNotPaused");
+      console.log("✅ Emergency withdrawal correctly requires a paused program");
+    }
+  });
+
+  it("Non-admin cannot pause the program", async () => {
+    try {
+      await program.methods
+        .setPaused(true)
+        .accounts({ admin: bob.publicKey, fund: fundPDA })
+        .signers([bob])
+        .rpc();
+      expect.fail("Expected transaction to fail but it succeeded");
+    } catch (error) {
+      expect(error.toString()).to.include("Unauthorized");
+      console.log("✅ Non-admin correctly prevented from pausing");
+    }
+  });
+
+  it("Pausing stops claims", async () => {
+    await program.methods
+      .setPaused(true)
+      .accounts({ admin: user, fund: fundPDA })
+      .rpc();
+
+    try {
+      await program.methods
+        .claimVested(new BN(vestingProposalId))
+        .accounts({ owner: user, round: roundPDA, fund: fundPDA, config: generateConfigPDA(program.programId) })
+        .rpc();
+      expect.fail("Expected transaction to fail but it succeeded");
+    } catch (error) {
+      expect(error.toString()).to.include("ProgramPaused");
+      console.log("✅ Claims correctly refused while paused");
+    }
+  });
+
+  it("Emergency withdrawal needs the admin threshold and the timelock", async () => {
+    const withdrawalPDA = generateEmergencyWithdrawalPDA(roundVaultPDA);
+    const amount = new BN(anchor.web3.LAMPORTS_PER_SOL);
+
+    await program.methods
+      .proposeEmergencyWithdrawal(new BN(ROUND_ID), amount, user, null)
+      .accounts({ vault: roundVaultPDA, admin: user, fund: fundPDA })
+      .rpc();
+
+    let withdrawal = await program.account.emergencyWithdrawal.fetch(withdrawalPDA);
+    expect(withdrawal.amount.toNumber()).to.equal(amount.toNumber());
+    expect(withdrawal.approvals.length).to.equal(1);
+    expect(withdrawal.threshold).to.equal(2);
+
+    // One approval is not enough
+    try {
+      await program.methods
+        .executeEmergencyWithdrawal(new BN(ROUND_ID))
+        .accounts({
+          vault: roundVaultPDA,
+          proposer: user,
+          destination: user,
+          fund: fundPDA,
+          mint: null,
+          vaultTokenAccount: null,
+          destinationTokenAccount: null,
+          ledger: null,
+          tokenProgram: null,
+        })
+        .rpc();
+      expect.fail("Expected transaction to fail but it succeeded");
+    } catch (error) {
+      expect(error.toString()).to.include("NotEnoughApprovals");
+    }
+
+    await program.methods
+      .approveEmergencyWithdrawal(new BN(ROUND_ID))
+      .accounts({ vault: roundVaultPDA, admin: admin2.publicKey, fund: fundPDA })
+      .signers([admin2])
+      .rpc();
+
+    try {
+      await program.methods
+        .approveEmergencyWithdrawal(new BN(ROUND_ID))
+        .accounts({ vault: roundVaultPDA, admin: admin2.publicKey, fund: fundPDA })
+        .signers([admin2])
+        .rpc();
+      expect.fail("Expected transaction to fail but it succeeded");
+    } catch (error) {
+      expect(error.toString()).to.include("AlreadyApproved");
+    }
+
+    withdrawal = await program.account.emergencyWithdrawal.fetch(withdrawalPDA);
+    expect(withdrawal.approvals.length).to.equal(2);
+
+    // Approved, but the two-day timelock is still running
+    try {
+      await program.methods
+        .executeEmergencyWithdrawal(new BN(ROUND_ID))
+        .accounts({
+          vault: roundVaultPDA,
+          proposer: user,
+          destination: user,
+          fund: fundPDA,
+          mint: null,
+          vaultTokenAccount: null,
+          destinationTokenAccount: null,
+          ledger: null,
+          tokenProgram: null,
+        })
+        .rpc();
+      expect.fail("Expected transaction to fail but it succeeded");
+    } catch (error) {
+      expect(error.toString()).to.include("TimelockNotElapsed");
+    }
+    console.log("✅ Emergency withdrawal gated by 2 approvals and the timelock");
+  });
+
+  it("Emergency withdrawal can move a token out of the vault", async () => {
+    const tokenVaultPDA = generateVaultPDA(TOKEN_ROUND_ID, program.programId);
+    const withdrawalPDA = generateEmergencyWithdrawalPDA(tokenVaultPDA);
+    const ledgerPDA = generateTokenLedgerPDA(tokenVaultPDA, treasuryMint, program.programId);
+    const vaultTokenPDA = generateVaultTokenPDA(tokenVaultPDA, treasuryMint, program.programId);
+    const amount = 1_000_000;
+
+    // local-testing builds accept a timelock of seconds
+    await program.methods
+      .setEmergencyPolicy({ threshold: 2, timelock: new BN(2) })
+      .accounts({ admin: user, fund: fundPDA })
+      .rpc();
+    await program.methods
+      .proposeEmergencyWithdrawal(new BN(TOKEN_ROUND_ID), new BN(amount), user, treasuryMint)
+      .accounts({ vault: tokenVaultPDA, admin: user, fund: fundPDA })
+      .rpc();
+    await program.methods
+      .approveEmergencyWithdrawal(new BN(TOKEN_ROUND_ID))
+      .accounts({ vault: tokenVaultPDA, admin: admin2.publicKey, fund: fundPDA })
+      .signers([admin2])
+      .rpc();
+
+    const withdrawal = await program.account.emergencyWithdrawal.fetch(withdrawalPDA);
+    expect(withdrawal.mint.toString()).to.equal(treasuryMint.toString());
+    expect(withdrawal.approvals.length).to.equal(2);
+    await new Promise((resolve) => setTimeout(resolve, 3000));
+
+    const accounts = {
+      vault: tokenVaultPDA,
+      proposer: user,
+      destination: user,
+      fund: fundPDA,
+      mint: treasuryMint,
+      vaultTokenAccount: vaultTokenPDA,
+      destinationTokenAccount: userTreasuryAccount,
+      ledger: ledgerPDA,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    };
+
+    // The ledger has to be there to record the withdrawal
+    try {
+      await program.methods
+        .executeEmergencyWithdrawal(new BN(TOKEN_ROUND_ID))
+        .accounts({ ...accounts, ledger: null })
+        .rpc();
+      expect.fail("Expected transaction to fail but it succeeded");
+    } catch (error) {
+      expect(error.toString()).to.include("TokenAccountsRequired");
+    }
+
+    const vaultBefore = await provider.connection.getTokenAccountBalance(vaultTokenPDA);
+    const ledgerBefore = await program.account.tokenLedger.fetch(ledgerPDA);
+    await program.methods
+      .executeEmergencyWithdrawal(new BN(TOKEN_ROUND_ID))
+      .accounts(accounts)
+      .rpc();
+    expect(await provider.connection.getAccountInfo(withdrawalPDA)).to.be.null;
+
+    const vaultAfter = await provider.connection.getTokenAccountBalance(vaultTokenPDA);
+    const remaining = Number(vaultAfter.value.amount);
+    expect(remaining).to.equal(Number(vaultBefore.value.amount) - amount);
+
+    // Recorded on the ledger, and no grant stays reserved against the tokens that left
+    const ledger = await program.account.tokenLedger.fetch(ledgerPDA);
+    expect(ledger.emergencyWithdrawn.toNumber()).to.equal(
+      ledgerBefore.emergencyWithdrawn.toNumber() + amount
+    );
+    expect(ledger.committed.toNumber()).to.equal(Math.min(ledgerBefore.committed.toNumber(), remaining));
+
+    await program.methods
+      .setEmergencyPolicy({ threshold: 2, timelock: new BN(2 * 86400) })
+      .accounts({ admin: user, fund: fundPDA })
+      .rpc();
+    console.log("✅ Token emergency withdrawal executed and recorded on the ledger");
+  });
+
+  it("Admin can cancel a pending emergency withdrawal and unpause", async () => {
+    const withdrawalPDA = generateEmergencyWithdrawalPDA(roundVaultPDA);
+    try {
+      await program.methods
+        .cancelEmergencyWithdrawal(new BN(ROUND_ID))
+        .accounts({ vault: roundVaultPDA, proposer: user, admin: bob.publicKey, fund: fundPDA })
+        .signers([bob])
+        .rpc();
+      expect.fail("Expected transaction to fail but it succeeded");
+    } catch (error) {
+      expect(error.toString()).to.include("Unauthorized");
+    }
+
+    // Admin 3 holds the treasurer and pauser roles
+    await program.methods
+      .cancelEmergencyWithdrawal(new BN(ROUND_ID))
+      .accounts({ vault: roundVaultPDA, proposer: user, admin: admin3.publicKey, fund: fundPDA })
+      .signers([admin3])
+      .rpc();
+    expect(await provider.connection.getAccountInfo(withdrawalPDA)).to.be.null;
+
+    await program.methods
+      .setPaused(false)
+      .accounts({ admin: user, fund: fundPDA })
+      .rpc();
+    const config = await program.account.config.fetch(generateConfigPDA(program.programId));
+    expect(config.paused).to.equal(false);
+    console.log("✅ Emergency withdrawal cancelled and program unpaused");
+  });
+
   // ==================== SUMMARY ====================
 
   it("Test summary", async () => {
@@ -1758,19 +3757,40 @@ describe("community-fund", () => {
     console.log("✅ User profile: initialization, duplicate prevention");
     console.log("✅ Proposals: create, update, count tracking");
     console.log("✅ Edge cases: max lengths, large amounts");
-    console.log("✅ Admin initialization: 3 admins, duplicate prevention");
+    console.log("✅ Admin initialization: admin council, duplicate prevention");
     console.log("✅ Proposal rejection: all 3 admins tested");
-    console.log("✅ Funding approval: single admin & 2-of-3 multisig");
-    console.log("✅ Admin transfer: position transfer, security checks");
+    console.log("✅ Funding approval: single admin & N-of-M multisig");
+    console.log("✅ Admin council: N-of-M members, changes need council approval and expire");
+    console.log("✅ Admin handover: nominate, nominee must sign accept, withdraw nomination");
+    console.log("✅ Roles: reviewer, treasurer, pauser and params gate privileged instructions");
     console.log("✅ Voting: single vote, multiple users, duplicate prevention");
     console.log("✅ Vote tracking: count increment, timestamp recording");
-    console.log("✅ Voting weights: various weights (0, 1, 100, 1000, 1M)");
+    console.log("✅ Vote escrow: lock, extend, early-unlock prevention");
+    console.log("✅ Voting weights: derived from locked tokens and lock duration");
     console.log("✅ Weight accumulation: multiple users with different weights");
+    console.log("✅ Vote choices: separate yes / no / abstain tallies");
+    console.log("✅ Vote changes: change choice, withdraw and re-vote");
+    console.log("✅ Delegation: combined weight, receipts, withdrawal, topic scope, revocation");
+    console.log("✅ Quadratic voting: n² credit cost, balance checks, refunds");
     console.log("✅ Vote data structure: timestamp, weight, bump storage");
-    console.log("✅ Vault: initialization, deposits, balance tracking");
-    console.log("✅ Finalization: time-based checks, vote threshold validation");
+    console.log("✅ Vault: one per round, deposits, balance tracking");
+    console.log("✅ Depositor records: per-depositor totals, count, timestamps and memo");
+    console.log("✅ Ragequit: shares priced at deposit, redeemed from the uncommitted vault balance");
+    console.log("✅ Commitments: approvals reserve vault SOL, released on claim, cancel or expiry");
+    console.log("✅ Rent reserve: claims and exits only spend lamports above rent exemption");
+    console.log("✅ Reconciliation: untracked inflows recorded as external donations");
+    console.log("✅ Emergency: pause stops payouts, SOL and token withdrawals need admin threshold + timelock");
+    console.log("✅ Finalization: time-based checks, quorum and approval ratio");
+    console.log("✅ Governance params: admin updates, validation, per-proposal snapshot");
+    console.log("✅ Status transitions: approve needs Finalized, claim needs Approved, owner cancel");
+    console.log("✅ Milestones: tranches sum to the request, submitted and approved before claim");
+    console.log("✅ Vesting: ordered schedule, linear claims, admin cancel of the unvested rest");
+    console.log("✅ Token treasury: per-mint vault accounts and ledgers, claim in the requested mint");
+    console.log("✅ Token-2022: interface transfers, fee-aware deposit, lock and unlock");
+    console.log("✅ Rounds: own vault, budget, application/voting windows and payout deadline");
+    console.log("✅ Funds: every account keyed under its fund, admins scoped to their own fund");
     console.log("✅ Claims: prevented before finalization, balance verification");
-    console.log("✅ Winner flow: complete conceptual flow demonstrated");
+    console.log("✅ Winner flow: SOL and token grants finalized, approved and claimed end to end");
     console.log("✅ Security: non-admin/non-owner prevention");
     console.log("✅ Multiple users: independent operations");
     console.log("=".repeat(60) + "\n");